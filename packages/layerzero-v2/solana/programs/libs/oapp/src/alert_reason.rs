@@ -0,0 +1,56 @@
+use anchor_lang::{prelude::*, solana_program::program_error::ProgramError};
+
+/// The first byte of the structured alert reasons. It can not start a utf-8 string, so they are
+/// told apart from the reasons of the stringified errors.
+pub const ALERT_REASON_PREFIX: u8 = 0xff;
+
+/// The structured reason of the lz_receive and lz_compose alerts of the executor
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub enum AlertReason {
+    /// The execution failed with the error
+    Failure(FailureReason),
+}
+
+/// The error of a failed instruction of the execution. The error code is the code of the custom
+/// errors, e.g. of anchor programs, and None for the built-in program errors.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct FailureReason {
+    pub program_id: Pubkey,
+    pub error_code: Option<u32>,
+    pub message: String,
+}
+
+impl FailureReason {
+    pub fn new(program_id: Pubkey, error: &ProgramError) -> Self {
+        let error_code = match error {
+            ProgramError::Custom(code) => Some(*code),
+            _ => None,
+        };
+        Self { program_id, error_code, message: error.to_string() }
+    }
+
+    /// Returns None if the reason is not a structured failure reason
+    pub fn decode(reason: &[u8]) -> Option<Self> {
+        match AlertReason::decode(reason) {
+            Some(AlertReason::Failure(failure)) => Some(failure),
+            _ => None,
+        }
+    }
+}
+
+impl AlertReason {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut reason = vec![ALERT_REASON_PREFIX];
+        // serializing to a vec does not fail
+        self.serialize(&mut reason).unwrap();
+        reason
+    }
+
+    /// Returns None if the reason is not a structured reason
+    pub fn decode(reason: &[u8]) -> Option<Self> {
+        match reason.split_first() {
+            Some((&ALERT_REASON_PREFIX, mut data)) => Self::deserialize(&mut data).ok(),
+            _ => None,
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+pub mod alert_reason;
 pub mod common;
 pub mod endpoint_cpi;
 pub mod lz_compose_types_v2;
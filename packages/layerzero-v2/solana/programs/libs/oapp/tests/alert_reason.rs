@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod test_alert_reason {
+    use anchor_lang::{prelude::*, solana_program::program_error::ProgramError};
+    use oapp::alert_reason::{AlertReason, FailureReason, ALERT_REASON_PREFIX};
+
+    #[test]
+    fn test_encode_decode() {
+        let failure = FailureReason::new(Pubkey::new_unique(), &ProgramError::Custom(1));
+        let reason = AlertReason::Failure(failure);
+        let encoded = reason.encode();
+        assert_eq!(encoded[0], ALERT_REASON_PREFIX);
+        assert_eq!(AlertReason::decode(&encoded), Some(reason));
+    }
+
+    #[test]
+    fn test_decode_string_reason() {
+        let reason = "Error processing Instruction 0: custom program error: 0x1".as_bytes();
+        assert_eq!(AlertReason::decode(reason), None);
+        assert_eq!(AlertReason::decode(&[]), None);
+        assert_eq!(AlertReason::decode(&[ALERT_REASON_PREFIX]), None);
+    }
+
+    #[test]
+    fn test_failure_reason_of_anchor_error() {
+        let program_id = Pubkey::new_unique();
+        let error: ProgramError = error!(oapp::ErrorCode::InvalidAddressLookupTable).into();
+        let failure = FailureReason::new(program_id, &error);
+        assert_eq!(failure.program_id, program_id);
+        assert_eq!(failure.error_code, Some(6000));
+
+        let encoded = AlertReason::Failure(failure.clone()).encode();
+        assert_eq!(FailureReason::decode(&encoded), Some(failure));
+    }
+
+    #[test]
+    fn test_failure_reason_of_built_in_error() {
+        let program_id = Pubkey::new_unique();
+        let failure = FailureReason::new(program_id, &ProgramError::InvalidArgument);
+        assert_eq!(failure.error_code, None);
+        assert_eq!(failure.message, ProgramError::InvalidArgument.to_string());
+
+        let encoded = AlertReason::Failure(failure.clone()).encode();
+        assert_eq!(FailureReason::decode(&encoded), Some(failure));
+    }
+}
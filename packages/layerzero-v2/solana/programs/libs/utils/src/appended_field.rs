@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use std::{
+    io::{Read, Write},
+    ops::Deref,
+};
+
+/// The header written before the appended fields: the discriminator and the version of their layout
+pub const APPENDED_HEADER_LEN: usize = 8 + 1;
+
+/// The fields appended to an account type after accounts of it were created
+pub trait AppendedFields: AnchorSerialize + AnchorDeserialize + Default {
+    /// Identifies the appended fields of the account type, written before them
+    const DISCRIMINATOR: [u8; 8];
+    /// The version of the layout of the appended fields, written after the discriminator
+    const VERSION: u8;
+}
+
+/// The appended fields of an account, written behind a header.
+///
+/// The accounts are serialized without zeroing the rest of their data, so the bytes after the
+/// original fields of an account created before the appended fields may be left from longer data.
+/// The appended fields are only read from an account created with them, or migrated to them by an
+/// instruction that zeroes the rest of its data and writes the header. An account without the
+/// header reads the default fields, which can not be set until it is migrated.
+#[derive(Clone, Default)]
+pub struct Appended<T> {
+    migrated: bool,
+    fields: T,
+}
+
+impl<T: AppendedFields> Appended<T> {
+    /// The appended fields of an account created with them
+    pub fn new(fields: T) -> Self {
+        Self { migrated: true, fields }
+    }
+
+    pub fn is_migrated(&self) -> bool {
+        self.migrated
+    }
+
+    /// Migrates the account to the default appended fields. The rest of the account data has to
+    /// be zeroed by `zero_trailing_data()`.
+    pub fn migrate(&mut self) {
+        self.migrated = true;
+    }
+
+    /// The appended fields to be set, None if the account is not migrated
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        if self.migrated {
+            Some(&mut self.fields)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Deref for Appended<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.fields
+    }
+}
+
+impl<T: AppendedFields> AnchorSerialize for Appended<T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if self.migrated {
+            writer.write_all(&T::DISCRIMINATOR)?;
+            T::VERSION.serialize(writer)?;
+            self.fields.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: AppendedFields> AnchorDeserialize for Appended<T> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        // the data of an account without the header ends here, or has zeroed or stale bytes left
+        let mut discriminator = [0u8; 8];
+        if reader.read_exact(&mut discriminator).is_err() || discriminator != T::DISCRIMINATOR {
+            return Ok(Self::default());
+        }
+        let version = u8::deserialize_reader(reader)?;
+        if version != T::VERSION {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "unknown version of the appended fields",
+            ));
+        }
+        Ok(Self::new(T::deserialize_reader(reader)?))
+    }
+}
+
+impl<T: Space> Space for Appended<T> {
+    const INIT_SPACE: usize = APPENDED_HEADER_LEN + T::INIT_SPACE;
+}
+
+/// Zeroes the account data after the serialized account, which may be left from longer data,
+/// before the account is migrated to its appended fields
+pub fn zero_trailing_data<T: AccountSerialize>(
+    account_info: &AccountInfo,
+    account: &T,
+) -> Result<()> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data)?;
+    account_info.try_borrow_mut_data()?[data.len()..].fill(0);
+    Ok(())
+}
+
+#[cfg(feature = "idl-build")]
+impl<T> anchor_lang::anchor_syn::idl::build::IdlBuild for Appended<T> {
+    fn __anchor_private_full_path() -> String {
+        format!("{}::{}", std::module_path!(), "Appended")
+    }
+
+    fn __anchor_private_gen_idl_type(
+    ) -> Option<anchor_lang::anchor_syn::idl::types::IdlTypeDefinition> {
+        use anchor_lang::anchor_syn::idl::types::*;
+
+        let field = |name: &str, ty| IdlField { name: name.into(), docs: None, ty };
+        Some(IdlTypeDefinition {
+            name: Self::__anchor_private_full_path(),
+            docs: Some(vec!["Absent on the accounts not migrated to the appended fields".into()]),
+            generics: Some(vec!["T".into()]),
+            ty: IdlTypeDefinitionTy::Struct {
+                fields: vec![
+                    field("discriminator", IdlType::Array(Box::new(IdlType::U8), 8)),
+                    field("version", IdlType::U8),
+                    field("fields", IdlType::Generic("T".into())),
+                ],
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Default, Debug, PartialEq, AnchorSerialize, AnchorDeserialize)]
+    struct TestFields {
+        a: u32,
+        b: Option<u64>,
+    }
+
+    impl AppendedFields for TestFields {
+        const DISCRIMINATOR: [u8; 8] = *b"testfild";
+        const VERSION: u8 = 1;
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    struct TestAccount {
+        v: Vec<u8>,
+        appended: Appended<TestFields>,
+    }
+
+    #[test]
+    fn test_appended_fields() {
+        let fields = TestFields { a: 1, b: Some(2) };
+        let account = TestAccount { v: vec![1, 2], appended: Appended::new(fields.clone()) };
+        let data = account.try_to_vec().unwrap();
+        assert_eq!(data.len(), 4 + 2 + APPENDED_HEADER_LEN + 4 + 9);
+
+        let read = TestAccount::deserialize(&mut &data[..]).unwrap();
+        assert!(read.appended.is_migrated());
+        assert_eq!(*read.appended, fields);
+
+        // an unknown version is an error
+        let mut data = data;
+        data[4 + 2 + 8] = 2;
+        assert!(TestAccount::deserialize(&mut &data[..]).is_err());
+        // so are the fields cut short
+        data[4 + 2 + 8] = 1;
+        assert!(TestAccount::deserialize(&mut &data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_appended_fields_not_migrated() {
+        let account = TestAccount { v: vec![1, 2], appended: Appended::default() };
+        let data = account.try_to_vec().unwrap();
+        assert_eq!(data.len(), 4 + 2);
+
+        // at the end of the data
+        let mut read = TestAccount::deserialize(&mut &data[..]).unwrap();
+        assert!(!read.appended.is_migrated());
+        assert_eq!(*read.appended, TestFields::default());
+        assert!(read.appended.get_mut().is_none());
+
+        // with the stale bytes of a longer vec left after the data
+        let stale = TestAccount {
+            v: vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
+            appended: Appended::default(),
+        };
+        let mut stale_data = stale.try_to_vec().unwrap();
+        stale_data[..data.len()].copy_from_slice(&data);
+        let read = TestAccount::deserialize(&mut &stale_data[..]).unwrap();
+        assert!(!read.appended.is_migrated());
+        assert_eq!(*read.appended, TestFields::default());
+
+        // with zeroed bytes
+        let zeroed = [data, vec![0; 32]].concat();
+        let read = TestAccount::deserialize(&mut &zeroed[..]).unwrap();
+        assert!(!read.appended.is_migrated());
+    }
+
+    #[test]
+    fn test_migrate_appended_fields() {
+        let mut account = TestAccount { v: vec![1], appended: Appended::default() };
+        account.appended.migrate();
+        account.appended.get_mut().unwrap().a = 3;
+
+        let data = account.try_to_vec().unwrap();
+        let read = TestAccount::deserialize(&mut &data[..]).unwrap();
+        assert!(read.appended.is_migrated());
+        assert_eq!(read.appended.a, 3);
+    }
+}
@@ -57,6 +57,46 @@ impl BytesUtils for &[u8] {
     }
 }
 
+/// A fallible reader over a byte slice. Every read advances the cursor and returns `None`
+/// instead of panicking when there are not enough bytes left.
+pub struct BytesCursor<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BytesCursor<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.offset.checked_add(len)?;
+        let bytes = self.bytes.get(self.offset..end)?;
+        self.offset = end;
+        Some(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Option<u8> {
+        self.read_bytes(1).map(|bytes| bytes[0])
+    }
+
+    pub fn read_u16(&mut self) -> Option<u16> {
+        self.read_bytes(2).map(|bytes| bytes.to_u16(0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,4 +264,27 @@ mod tests {
         ][..];
         assert_eq!(bytes.to_byte_array::<32>(1), [1_u8; 32]);
     }
+
+    #[test]
+    fn test_cursor_reads() {
+        let mut cursor = BytesCursor::new(&[1_u8, 1, 2, 3, 4][..]);
+        assert_eq!(cursor.read_u8(), Some(1));
+        assert_eq!(cursor.read_u16(), Some(2_u16.pow(8) + 2));
+        assert_eq!(cursor.offset(), 3);
+        assert_eq!(cursor.read_bytes(2), Some(&[3_u8, 4][..]));
+        assert!(cursor.is_empty());
+    }
+
+    #[test]
+    fn test_cursor_out_of_bounds() {
+        let mut cursor = BytesCursor::new(&[1_u8, 2, 3][..]);
+        assert_eq!(cursor.read_bytes(4), None);
+        // a failed read does not advance the cursor
+        assert_eq!(cursor.offset(), 0);
+        assert_eq!(cursor.read_bytes(2), Some(&[1_u8, 2][..]));
+        assert_eq!(cursor.read_u16(), None);
+        assert_eq!(cursor.read_u8(), Some(3));
+        assert_eq!(cursor.read_u8(), None);
+        assert_eq!(cursor.read_bytes(usize::MAX), None);
+    }
 }
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, sysvar::instructions};
+
+// the offsets of a signature in the ed25519 program instruction data, after the 2-byte header
+pub const SIGNATURE_OFFSETS_START: usize = 2;
+pub const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+pub const PUBKEY_SERIALIZED_SIZE: usize = 32;
+// the instruction index of the signature data in the ed25519 instruction itself
+const CURRENT_INSTRUCTION_INDEX: u16 = u16::MAX;
+
+/// Returns the signers and messages of the signatures verified by the ed25519 program instructions
+/// of the transaction. The transaction fails if any of the signatures are invalid, so the
+/// instructions are introspected by the instructions sysvar instead of verifying them again.
+pub fn verified_ed25519_signatures(
+    instructions_sysvar: &AccountInfo,
+) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+    require_keys_eq!(instructions_sysvar.key(), instructions::ID, ErrorCode::AccountSysvarMismatch);
+
+    let mut signatures = Vec::new();
+    let mut index = 0;
+    while let Ok(ix) = instructions::load_instruction_at_checked(index, instructions_sysvar) {
+        if ix.program_id == ed25519_program::ID {
+            signatures.extend(
+                ed25519_signatures(&ix.data)
+                    .into_iter()
+                    .map(|(signer, message)| (signer, message.to_vec())),
+            );
+        }
+        index += 1;
+    }
+    Ok(signatures)
+}
+
+/// Returns the signers and messages of the signatures in the ed25519 program instruction data.
+/// Only the signatures with all their data in the instruction itself are returned.
+pub fn ed25519_signatures(data: &[u8]) -> Vec<(Pubkey, &[u8])> {
+    let count = data.first().copied().unwrap_or_default() as usize;
+    (0..count)
+        .filter_map(|i| {
+            let start = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+            let offsets = data.get(start..start + SIGNATURE_OFFSETS_SERIALIZED_SIZE)?;
+            let read = |n: usize| u16::from_le_bytes([offsets[n * 2], offsets[n * 2 + 1]]);
+            // signature offset, signature ix index, pubkey offset, pubkey ix index,
+            // message offset, message size, message ix index
+            if [read(1), read(3), read(6)].iter().any(|ix| *ix != CURRENT_INSTRUCTION_INDEX) {
+                return None;
+            }
+            let pubkey_offset = read(2) as usize;
+            let pubkey = data.get(pubkey_offset..pubkey_offset + PUBKEY_SERIALIZED_SIZE)?;
+            let message_offset = read(4) as usize;
+            let message = data.get(message_offset..message_offset + read(5) as usize)?;
+            Some((Pubkey::try_from(pubkey).ok()?, message))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // builds the instruction data of the ed25519 program with the signatures in the instruction
+    fn ed25519_instruction_data(signers: &[(Pubkey, &[u8])], ix_index: u16) -> Vec<u8> {
+        let mut data = vec![signers.len() as u8, 0];
+        let mut offset =
+            SIGNATURE_OFFSETS_START + signers.len() * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        let mut payload = Vec::new();
+        for (signer, message) in signers {
+            let pubkey_offset = offset;
+            let signature_offset = pubkey_offset + PUBKEY_SERIALIZED_SIZE;
+            let message_offset = signature_offset + 64;
+            for value in [
+                signature_offset as u16,
+                ix_index,
+                pubkey_offset as u16,
+                ix_index,
+                message_offset as u16,
+                message.len() as u16,
+                ix_index,
+            ] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+            payload.extend_from_slice(&signer.to_bytes());
+            payload.extend_from_slice(&[0u8; 64]);
+            payload.extend_from_slice(message);
+            offset = message_offset + message.len();
+        }
+        data.extend(payload);
+        data
+    }
+
+    #[test]
+    fn test_ed25519_signatures() {
+        let (signer1, signer2) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let data = ed25519_instruction_data(
+            &[(signer1, &[1u8; 32]), (signer2, &[2u8; 3])],
+            CURRENT_INSTRUCTION_INDEX,
+        );
+        assert_eq!(
+            ed25519_signatures(&data),
+            vec![(signer1, &[1u8; 32][..]), (signer2, &[2u8; 3][..])]
+        );
+
+        // the signature data in other instructions is not returned
+        let data = ed25519_instruction_data(&[(signer1, &[1u8; 32])], 0);
+        assert!(ed25519_signatures(&data).is_empty());
+
+        // truncated data
+        assert!(ed25519_signatures(&data[..20]).is_empty());
+        assert!(ed25519_signatures(&[]).is_empty());
+    }
+}
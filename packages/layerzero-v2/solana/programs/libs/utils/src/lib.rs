@@ -1,3 +1,5 @@
+pub mod appended_field;
 pub mod bytes_lib;
+pub mod ed25519;
 pub mod macros;
 pub mod sorted_list_helper;
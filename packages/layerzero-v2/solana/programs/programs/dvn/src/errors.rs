@@ -19,4 +19,12 @@ pub enum DvnError {
     UnexpiredExecuteHash,
     InvalidAmount,
     EidNotSupported,
+    InvalidAclPage,
+    TooManyPagedAcls,
+    InvalidFeeLedger,
+    InvalidSignerOverlap,
+    InvalidOptionSize,
+    ComputeExceedsCap,
+    NotMigrated,
+    AlreadyMigrated,
 }
@@ -14,4 +14,14 @@ pub struct MultisigConfigSetEvent {
 pub struct FeeWithdrawnEvent {
     pub receiver: Pubkey,
     pub amount: u64,
+    // the part of the amount by destination, attributed from the fees claimed to the dvn in the uln
+    pub fees: Vec<EidFee>,
+}
+
+#[event]
+pub struct OAppPricingSetEvent {
+    pub oapp: Pubkey,
+    pub dst_eid: u32,
+    pub multiplier_bps: Option<u16>,
+    pub floor_margin_usd: Option<u128>,
 }
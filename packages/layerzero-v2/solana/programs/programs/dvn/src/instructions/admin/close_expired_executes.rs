@@ -0,0 +1,49 @@
+use crate::*;
+
+/// Closes the expired execute hash accounts in the remaining accounts and refunds their rent to
+/// the receiver. The unexpired accounts, the accounts already closed and the other accounts of the
+/// dvn are skipped, so the accounts can be collected in bulk.
+#[derive(Accounts)]
+pub struct CloseExpiredExecutes<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [DVN_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admins.contains(admin.key) @DvnError::NotAdmin
+    )]
+    pub config: Account<'info, DvnConfig>,
+    /// CHECK: the receiver of the rent
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+impl CloseExpiredExecutes<'_> {
+    pub fn apply<'c: 'info, 'info>(
+        ctx: &mut Context<'_, '_, 'c, 'info, CloseExpiredExecutes<'info>>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        close_expired_executes(ctx.remaining_accounts, &ctx.accounts.receiver, now)
+    }
+}
+
+/// Closes the accounts that are expired execute hashes at `now`. Any other account, e.g. the dvn
+/// config or an acl page, is skipped rather than failing the whole batch.
+pub fn close_expired_executes<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    receiver: &AccountInfo<'info>,
+    now: i64,
+) -> Result<()> {
+    for account in accounts {
+        if account.owner != &ID {
+            continue;
+        }
+        // the execute hash accounts are only created by invoke(), so the owner and the
+        // discriminator are checked instead of the seeds of the digest hash
+        if let Ok(execute_hash) = Account::<ExecuteHash>::try_from(account) {
+            if execute_hash.expiration <= now {
+                execute_hash.close(receiver.clone())?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use crate::*;
+
+/// Creates an empty page of the allowlist of a destination. The oapps on it are set by quorum
+/// with MultisigConfig::AclPage.
+#[derive(Accounts)]
+#[instruction(params: InitAclPageParams)]
+pub struct InitAclPage<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [DVN_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admins.contains(admin.key) @DvnError::NotAdmin
+    )]
+    pub config: Account<'info, DvnConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + AclPage::INIT_SPACE,
+        seeds = [ACL_PAGE_SEED, &params.dst_eid.to_be_bytes(), &[params.page]],
+        bump
+    )]
+    pub acl_page: Account<'info, AclPage>,
+    pub system_program: Program<'info, System>,
+}
+
+impl InitAclPage<'_> {
+    pub fn apply(ctx: &mut Context<InitAclPage>, params: &InitAclPageParams) -> Result<()> {
+        ctx.accounts.acl_page.dst_eid = params.dst_eid;
+        ctx.accounts.acl_page.page = params.page;
+        ctx.accounts.acl_page.bump = ctx.bumps.acl_page;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct InitAclPageParams {
+    pub dst_eid: u32,
+    pub page: u8,
+}
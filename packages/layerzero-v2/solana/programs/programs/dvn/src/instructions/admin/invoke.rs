@@ -1,8 +1,8 @@
 use crate::*;
 use anchor_lang::solana_program::{
-    instruction::Instruction, keccak, program::invoke_signed,
-    secp256k1_recover::SECP256K1_PUBLIC_KEY_LENGTH,
+    instruction::Instruction, keccak, program::invoke_signed, sysvar,
 };
+use utils::ed25519::verified_ed25519_signatures;
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -23,14 +23,28 @@ pub struct Invoke<'info> {
     pub system_program: Program<'info, System>,
 }
 
-impl Invoke<'_> {
-    pub fn apply(ctx: &mut Context<Invoke>, params: &InvokeParams) -> Result<()> {
+impl<'info> Invoke<'info> {
+    pub fn apply<'c: 'info>(
+        ctx: &mut Context<'_, '_, 'c, 'info, Invoke<'info>>,
+        params: &InvokeParams,
+    ) -> Result<()> {
         require!(ctx.accounts.config.vid == params.digest.vid, DvnError::InvalidVid);
         require!(params.digest.expiration > Clock::get()?.unix_timestamp, DvnError::Expired);
 
-        // verify signatures
+        // verify signatures, the instructions sysvar is in the remaining accounts if any ed25519
+        // signers signed
         let hash = keccak::hash(&params.digest.data()?).to_bytes();
-        ctx.accounts.config.multisig.verify_signatures(&params.signatures, &hash)?;
+        let ed25519_signatures = match ctx
+            .remaining_accounts
+            .iter()
+            .find(|account| account.key() == sysvar::instructions::ID)
+        {
+            Some(instructions_sysvar) => verified_ed25519_signatures(instructions_sysvar)?,
+            None => Vec::new(),
+        };
+        ctx.accounts
+            .config
+            .verify_signatures(&params.signatures, &ed25519_signatures, &hash)?;
 
         // mark the execute hash as executed
         ctx.accounts.execute_hash.expiration = params.digest.expiration;
@@ -50,8 +64,8 @@ impl Invoke<'_> {
                 );
             }
 
-            // apply the config
-            config.apply(&mut ctx.accounts.config)?;
+            // apply the config, the acl pages it sets are in the remaining accounts
+            config.apply(&mut ctx.accounts.config, ctx.remaining_accounts)?;
             emit_cpi!(MultisigConfigSetEvent { config });
         } else {
             // the signer must be an admin
@@ -157,11 +171,35 @@ pub enum MultisigConfig {
     Msglibs(Vec<Pubkey>),
     Paused(bool),
     Quorum(u8),
-    Signers(Vec<[u8; SECP256K1_PUBLIC_KEY_LENGTH]>),
+    Signers(Vec<SignerKey>),
+    AclPage(AclPageConfig),
+    RotateSigners(RotateSignersConfig),
+}
+
+/// Rotates the signers and sets the quorum atomically. The removed signers are still counted
+/// towards the quorum for the optional overlap seconds.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct RotateSignersConfig {
+    pub add: Vec<SignerKey>,
+    pub remove: Vec<SignerKey>,
+    pub new_quorum: u8,
+    pub overlap_seconds: Option<u32>,
+}
+
+/// Adds the oapps not on the page of the allowlist of the destination, and removes the oapps on it
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct AclPageConfig {
+    pub dst_eid: u32,
+    pub page: u8,
+    pub oapps: Vec<Pubkey>,
 }
 
 impl MultisigConfig {
-    pub fn apply(&self, config: &mut DvnConfig) -> Result<()> {
+    pub fn apply<'info>(
+        &self,
+        config: &mut DvnConfig,
+        accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
         match self {
             MultisigConfig::Admins(admins) => {
                 config.set_admins(admins.clone())?;
@@ -183,12 +221,41 @@ impl MultisigConfig {
                 config.paused = *paused;
             },
             MultisigConfig::Quorum(quorum) => {
-                let signers = config.multisig.signers.clone();
-                config.set_multisig(Multisig { quorum: *quorum, signers })?;
+                config.set_signers(&config.signers(), *quorum)?;
             },
             MultisigConfig::Signers(signers) => {
-                let quorum = config.multisig.quorum;
-                config.set_multisig(Multisig { quorum, signers: signers.clone() })?;
+                config.set_signers(signers, config.multisig.quorum)?;
+            },
+            MultisigConfig::AclPage(AclPageConfig { dst_eid, page, oapps }) => {
+                let mut acl_page = accounts
+                    .iter()
+                    .filter_map(|account| Account::<AclPage>::try_from(account).ok())
+                    .find(|acl_page| acl_page.dst_eid == *dst_eid && acl_page.page == *page)
+                    .ok_or(DvnError::InvalidAclPage)?;
+                let old_len = acl_page.allow_list.len();
+                for oapp in oapps {
+                    acl_page.set_allowlist(oapp)?;
+                }
+                config.appended_mut()?.paged_acl.update(
+                    *dst_eid,
+                    old_len,
+                    acl_page.allow_list.len(),
+                )?;
+                acl_page.exit(&ID)?;
+            },
+            MultisigConfig::RotateSigners(RotateSignersConfig {
+                add,
+                remove,
+                new_quorum,
+                overlap_seconds,
+            }) => {
+                config.rotate_signers(
+                    add,
+                    remove,
+                    *new_quorum,
+                    *overlap_seconds,
+                    Clock::get()?.unix_timestamp,
+                )?;
             },
         }
         Ok(())
@@ -1,11 +1,19 @@
 pub mod close_execute;
+pub mod close_expired_executes;
 pub mod extend_dvn_config;
+pub mod init_acl_page;
 pub mod invoke;
+pub mod migrate_dvn_config;
 pub mod set_config;
+pub mod set_oapp_pricing;
 pub mod withdraw_fee;
 
 pub use close_execute::*;
+pub use close_expired_executes::*;
 pub use extend_dvn_config::*;
+pub use init_acl_page::*;
 pub use invoke::*;
+pub use migrate_dvn_config::*;
 pub use set_config::*;
+pub use set_oapp_pricing::*;
 pub use withdraw_fee::*;
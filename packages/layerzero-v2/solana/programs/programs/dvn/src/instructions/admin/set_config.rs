@@ -1,4 +1,5 @@
 use crate::*;
+use worker_interface::worker_utils::FeeToken;
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -16,11 +17,7 @@ pub struct SetConfig<'info> {
 impl SetConfig<'_> {
     pub fn apply(ctx: &mut Context<SetConfig>, params: &SetConfigParams) -> Result<()> {
         let account_size = ctx.accounts.config.to_account_info().data_len();
-        let dst_configs_max_len = if account_size > (DvnConfig::INIT_SPACE + 8) {
-            DST_CONFIG_MAX_LEN
-        } else {
-            DST_CONFIG_DEFAULT_LEN
-        };
+        let dst_configs_max_len = DvnConfig::dst_configs_max_len(account_size);
         params.config.apply(dst_configs_max_len, &mut ctx.accounts.config)?;
         emit_cpi!(AdminConfigSetEvent { config: params.config.clone() });
         Ok(())
@@ -39,6 +36,9 @@ pub enum AdminConfig {
     DstConfigs(Vec<DstConfig>),
     PriceFeed(Pubkey),
     RemoveDstConfigs(Vec<u32>),
+    FeeToken(Option<FeeToken>),
+    DstComputeConfigs(Vec<DstComputeConfig>),
+    RemoveDstComputeConfigs(Vec<u32>),
 }
 
 impl AdminConfig {
@@ -59,6 +59,15 @@ impl AdminConfig {
             AdminConfig::RemoveDstConfigs(dst_eids) => {
                 config.remove_dst_configs(dst_eids.clone())?;
             },
+            AdminConfig::FeeToken(fee_token) => {
+                config.appended_mut()?.fee_token = fee_token.clone();
+            },
+            AdminConfig::DstComputeConfigs(configs) => {
+                config.set_dst_compute_configs(configs.clone())?;
+            },
+            AdminConfig::RemoveDstComputeConfigs(dst_eids) => {
+                config.remove_dst_compute_configs(dst_eids.clone())?;
+            },
         }
         Ok(())
     }
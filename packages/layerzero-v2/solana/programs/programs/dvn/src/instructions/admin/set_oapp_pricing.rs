@@ -0,0 +1,50 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: SetOAppPricingParams)]
+pub struct SetOAppPricing<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [DVN_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admins.contains(admin.key) @DvnError::NotAdmin
+    )]
+    pub config: Account<'info, DvnConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + OAppPricing::INIT_SPACE,
+        seeds = [OAPP_PRICING_SEED, params.oapp.as_ref(), &params.dst_eid.to_be_bytes()],
+        bump
+    )]
+    pub oapp_pricing: Account<'info, OAppPricing>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetOAppPricing<'_> {
+    pub fn apply(ctx: &mut Context<SetOAppPricing>, params: &SetOAppPricingParams) -> Result<()> {
+        let oapp_pricing = &mut ctx.accounts.oapp_pricing;
+        oapp_pricing.oapp = params.oapp;
+        oapp_pricing.dst_eid = params.dst_eid;
+        oapp_pricing.bump = ctx.bumps.oapp_pricing;
+        oapp_pricing.multiplier_bps = params.multiplier_bps;
+        oapp_pricing.floor_margin_usd = params.floor_margin_usd;
+        emit_cpi!(OAppPricingSetEvent {
+            oapp: params.oapp,
+            dst_eid: params.dst_eid,
+            multiplier_bps: params.multiplier_bps,
+            floor_margin_usd: params.floor_margin_usd,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetOAppPricingParams {
+    pub oapp: Pubkey,
+    pub dst_eid: u32,
+    pub multiplier_bps: Option<u16>,
+    pub floor_margin_usd: Option<u128>,
+}
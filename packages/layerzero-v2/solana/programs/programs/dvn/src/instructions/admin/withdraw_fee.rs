@@ -1,5 +1,8 @@
 use crate::*;
+use uln::state::ClaimedFees;
 
+/// The withdrawn amount is attributed to the destinations by the fees claimed to the dvn from its
+/// fee ledgers in the uln, reported with the withdrawal.
 #[event_cpi]
 #[derive(Accounts)]
 pub struct WithdrawFee<'info> {
@@ -13,26 +16,46 @@ pub struct WithdrawFee<'info> {
     pub config: Account<'info, DvnConfig>,
     #[account(mut)]
     pub receiver: UncheckedAccount<'info>,
+    /// CHECK: the fees claimed to the dvn in the uln, read if created by the uln
+    #[account(
+        seeds = [uln::CLAIMED_FEES_SEED, config.key().as_ref()],
+        bump,
+        seeds::program = uln::ID
+    )]
+    pub claimed_fees: UncheckedAccount<'info>,
 }
 
 impl WithdrawFee<'_> {
     pub fn apply(ctx: &mut Context<WithdrawFee>, params: &WithdrawFeeParams) -> Result<()> {
-        let required_lamports = Rent::get()?.minimum_balance(8 + DvnConfig::INIT_SPACE);
-        let surplus_lamports = ctx.accounts.config.get_lamports() - required_lamports;
+        let config_acc = ctx.accounts.config.to_account_info();
+        let required_lamports = Rent::get()?.minimum_balance(config_acc.data_len());
+        let surplus_lamports = config_acc.get_lamports().saturating_sub(required_lamports);
         require!(surplus_lamports >= params.amount, DvnError::InvalidAmount);
 
         ctx.accounts.config.sub_lamports(params.amount)?;
         ctx.accounts.receiver.add_lamports(params.amount)?;
 
+        let claimed = claimed_fees(&ctx.accounts.claimed_fees)?;
+        let fees = ctx.accounts.config.attribute_withdrawal(&claimed, params.amount);
         emit_cpi!(FeeWithdrawnEvent {
             receiver: ctx.accounts.receiver.key(),
             amount: params.amount,
+            fees,
         });
 
         Ok(())
     }
 }
 
+/// The fees claimed to the dvn by destination, none if the account is not created by the uln
+pub fn claimed_fees(claimed_fees_acc: &AccountInfo) -> Result<Vec<EidFee>> {
+    if claimed_fees_acc.owner != &uln::ID {
+        return Ok(vec![]);
+    }
+    let claimed_fees = ClaimedFees::try_deserialize(&mut &claimed_fees_acc.try_borrow_data()?[..])?;
+    Ok(claimed_fees.fees.fees)
+}
+
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct WithdrawFeeParams {
     pub amount: u64,
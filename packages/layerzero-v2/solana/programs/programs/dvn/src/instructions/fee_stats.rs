@@ -0,0 +1,34 @@
+use crate::*;
+use std::collections::HashSet;
+use uln::state::{FeeLedger, LedgerFees};
+
+/// The lifetime fees of the dvn by destination, accrued in the fee ledger shards of the dvn in the
+/// uln passed as the remaining accounts. The fees paid to the dvn config account directly are not
+/// attributed.
+#[derive(Accounts)]
+pub struct GetFeeStats<'info> {
+    #[account(seeds = [DVN_CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, DvnConfig>,
+}
+
+impl GetFeeStats<'_> {
+    pub fn apply(ctx: &Context<GetFeeStats>) -> Result<Vec<EidFee>> {
+        sum_fee_ledgers(&ctx.accounts.config.key(), ctx.remaining_accounts)
+    }
+}
+
+/// Sums the lifetime fees by destination of the fee ledgers of the dvn in the uln
+pub fn sum_fee_ledgers(dvn: &Pubkey, fee_ledgers: &[AccountInfo]) -> Result<Vec<EidFee>> {
+    let mut fees = LedgerFees::default();
+    let mut unique = HashSet::new();
+    for fee_ledger_acc in fee_ledgers {
+        require!(unique.insert(fee_ledger_acc.key()), DvnError::InvalidFeeLedger);
+        require!(fee_ledger_acc.owner == &uln::ID, DvnError::InvalidFeeLedger);
+        let fee_ledger = FeeLedger::try_deserialize(&mut &fee_ledger_acc.try_borrow_data()?[..])?;
+        require!(fee_ledger.worker == *dvn, DvnError::InvalidFeeLedger);
+        for fee in &fee_ledger.fees.fees {
+            fees.add(fee.eid, fee.amount);
+        }
+    }
+    Ok(fees.fees)
+}
@@ -19,11 +19,10 @@ impl InitDvn<'_> {
     pub fn apply(ctx: &mut Context<InitDvn>, params: &InitDvnParams) -> Result<()> {
         ctx.accounts.config.vid = params.vid;
         ctx.accounts.config.bump = ctx.bumps.config;
+        ctx.accounts.config.appended.migrate();
 
         // set quorum and signers
-        ctx.accounts
-            .config
-            .set_multisig(Multisig { signers: params.signers.clone(), quorum: params.quorum })?;
+        ctx.accounts.config.set_signers(&params.signers, params.quorum)?;
 
         ctx.accounts.config.set_admins(params.admins.clone())?;
         ctx.accounts.config.set_msglibs(params.msglibs.clone())?;
@@ -40,7 +39,7 @@ pub struct InitDvnParams {
     vid: u32,
     msglibs: Vec<Pubkey>,
     price_feed: Pubkey,
-    signers: Vec<[u8; 64]>,
+    signers: Vec<SignerKey>,
     quorum: u8,
     admins: Vec<Pubkey>,
 }
@@ -1,9 +1,13 @@
 pub mod admin;
+pub mod fee_stats;
 pub mod init_dvn;
 pub mod quote;
 pub mod verifiable;
+pub mod verify;
 
 pub use admin::*;
+pub use fee_stats::*;
 pub use init_dvn::*;
 pub use quote::*;
 pub use verifiable::*;
+pub use verify::*;
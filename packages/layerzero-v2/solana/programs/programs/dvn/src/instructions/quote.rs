@@ -1,8 +1,13 @@
 use crate::*;
 use pricefeed::instructions::GetFeeParams;
 use utils::sorted_list_helper;
-use worker_interface::worker_utils;
+use worker_interface::{worker_utils, TokenFee, WorkerError};
 
+/// The remaining accounts are optional: the oapp pricing of the sender to the destination, and
+/// the page of the allowlist of the destination with the sender if the destination has a paged
+/// allowlist. The uln forwards them from the trailing accounts of its quote and send. They are
+/// only taken at the addresses derived from the sender and the destination, and message libraries
+/// that do not pass them quote with the dst config only.
 #[derive(Accounts)]
 #[instruction(params: QuoteDvnParams)]
 pub struct Quote<'info> {
@@ -14,18 +19,25 @@ pub struct Quote<'info> {
     pub price_feed_config: AccountInfo<'info>,
 }
 
-impl Quote<'_> {
-    pub fn apply(ctx: &Context<Quote>, params: &QuoteDvnParams) -> Result<u64> {
+impl<'info> Quote<'info> {
+    pub fn apply<'c: 'info>(
+        ctx: &Context<'_, '_, 'c, 'info, Quote<'info>>,
+        params: &QuoteDvnParams,
+    ) -> Result<u64> {
         let config = &ctx.accounts.dvn_config;
         require!(!config.paused, DvnError::Paused);
 
-        config.acl.assert_permission(&params.sender)?;
-        if config.msglibs.len() > 0 {
+        if config.appended.paged_acl.is_paged(params.dst_eid) {
+            // the paged allowlist of the destination takes the place of the allowlist
             require!(
-                config.msglibs.binary_search(&params.msglib).is_ok(),
-                DvnError::MsgLibNotAllowed
+                config.acl.deny_list.binary_search(&params.sender).is_err()
+                    && on_acl_page(ctx.remaining_accounts, params.dst_eid, &params.sender),
+                WorkerError::PermissionDenied
             );
+        } else {
+            config.acl.assert_permission(&params.sender)?;
         }
+        require!(config.is_msglib_allowed(&params.msglib), DvnError::MsgLibNotAllowed);
 
         let total_signature_bytes = config.multisig.quorum as u64 * SIGNATURE_RAW_BYTES as u64;
         let total_signature_bytes_padded = if total_signature_bytes % 32 == 0 {
@@ -36,17 +48,32 @@ impl Quote<'_> {
 
         // getFee should charge on execute(updateHash)
         // totalSignatureBytesPadded also has 64 overhead for bytes
-        let calldata_size = EXECUTE_FIXED_BYTES + VERIFY_BYTES + total_signature_bytes_padded + 64;
+        let mut calldata_size =
+            EXECUTE_FIXED_BYTES + VERIFY_BYTES + total_signature_bytes_padded + 64;
 
         let dst_config =
             sorted_list_helper::get_from_sorted_list_by_eid(&config.dst_configs, params.dst_eid)?;
         require!(dst_config.dst_gas > 0, DvnError::EidNotSupported);
+        let mut total_gas = dst_config.dst_gas as u128;
 
-        let get_fee_params = GetFeeParams {
-            dst_eid: params.dst_eid,
-            calldata_size,
-            total_gas: dst_config.dst_gas as u128,
-        };
+        // the compute options are charged by the compute config of the destination
+        let (compute_units, response_size) = compute_options(params)?;
+        if compute_units > 0 || response_size > 0 {
+            let compute_config = sorted_list_helper::get_from_sorted_list_by_eid(
+                &config.appended.dst_compute_configs,
+                params.dst_eid,
+            )?;
+            require!(
+                compute_units <= compute_config.max_compute_units as u128
+                    && response_size <= compute_config.max_response_size as u128,
+                DvnError::ComputeExceedsCap
+            );
+            total_gas += compute_units * compute_config.gas_per_compute_unit as u128
+                + response_size * compute_config.gas_per_response_byte as u128;
+            calldata_size += response_size as u64;
+        }
+
+        let get_fee_params = GetFeeParams { dst_eid: params.dst_eid, calldata_size, total_gas };
         let cpi_ctx = CpiContext::new(
             ctx.accounts.price_feed_program.to_account_info(),
             pricefeed::cpi::accounts::GetFee {
@@ -56,19 +83,78 @@ impl Quote<'_> {
         let (fee, _, _, native_token_price_usd) =
             pricefeed::cpi::get_fee(cpi_ctx, get_fee_params)?.get();
 
-        let multiplier_bps = if let Some(multiplier_bps) = dst_config.multiplier_bps {
-            multiplier_bps
-        } else {
-            config.default_multiplier_bps
+        // the oapp pricing of the sender takes precedence over the dst config
+        let oapp_pricing = oapp_pricing(ctx.remaining_accounts, params.dst_eid, &params.sender);
+        let (oapp_multiplier_bps, oapp_floor_margin_usd) = match &oapp_pricing {
+            Some(pricing) => (pricing.multiplier_bps, pricing.floor_margin_usd),
+            None => (None, None),
         };
 
+        let multiplier_bps = oapp_multiplier_bps
+            .or(dst_config.multiplier_bps)
+            .unwrap_or(config.default_multiplier_bps);
+
         let fee = worker_utils::increase_fee_with_multiplier_or_floor_margin(
             fee,
             multiplier_bps as u128,
-            dst_config.floor_margin_usd,
+            oapp_floor_margin_usd.or(dst_config.floor_margin_usd),
             native_token_price_usd,
         );
 
         Ok(worker_utils::safe_convert_u128_to_u64(fee)?)
     }
+
+    pub fn apply_in_token<'c: 'info>(
+        ctx: &Context<'_, '_, 'c, 'info, Quote<'info>>,
+        params: &QuoteDvnParams,
+    ) -> Result<Option<TokenFee>> {
+        match &ctx.accounts.dvn_config.appended.fee_token {
+            Some(fee_token) => Ok(Some(fee_token.convert(Self::apply(ctx, params)?)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Sums the declared compute units and response sizes of the compute options. The other option
+/// types are not priced.
+fn compute_options(params: &QuoteDvnParams) -> Result<(u128, u128)> {
+    let mut compute_units: u128 = 0;
+    let mut response_size: u128 = 0;
+    for option in &params.options {
+        if option.option_type == OPTION_TYPE_COMPUTE {
+            let (units, size) = decode_compute_params(&option.params)?;
+            compute_units += units as u128;
+            response_size += size as u128;
+        }
+    }
+    Ok((compute_units, response_size))
+}
+
+/// Whether the oapp is on a page of the allowlist of the destination. Each page is only taken at
+/// the address derived from the destination and its page number.
+fn on_acl_page<'info>(accounts: &'info [AccountInfo<'info>], dst_eid: u32, oapp: &Pubkey) -> bool {
+    accounts.iter().any(|account| {
+        Account::<AclPage>::try_from(account).map_or(false, |page| {
+            let seeds: &[&[u8]] =
+                &[ACL_PAGE_SEED, &dst_eid.to_be_bytes(), &[page.page], &[page.bump]];
+            Pubkey::create_program_address(seeds, &ID).map_or(false, |key| key == account.key())
+                && page.contains(oapp)
+        })
+    })
+}
+
+/// The oapp pricing of the oapp to the destination, at the address derived from both
+fn oapp_pricing<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    dst_eid: u32,
+    oapp: &Pubkey,
+) -> Option<Account<'info, OAppPricing>> {
+    let (key, _) = Pubkey::find_program_address(
+        &[OAPP_PRICING_SEED, oapp.as_ref(), &dst_eid.to_be_bytes()],
+        &ID,
+    );
+    accounts
+        .iter()
+        .find(|account| account.key() == key)
+        .and_then(|account| Account::<OAppPricing>::try_from(account).ok())
 }
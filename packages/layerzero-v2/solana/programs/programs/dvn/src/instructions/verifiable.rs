@@ -1,17 +1,12 @@
 use crate::*;
-use anchor_lang::solana_program::keccak;
 use messagelib_helper::{
     endpoint::{
         state::{Nonce, PayloadHash, PENDING_INBOUND_NONCE_MAX_LEN},
-        ID as ENDPOINT_ID, NONCE_SEED, PAYLOAD_HASH_SEED,
+        NONCE_SEED, PAYLOAD_HASH_SEED,
     },
+    messagelib_interface,
     packet_v1_codec::{self, PACKET_HEADER_SIZE},
 };
-use uln::{
-    instructions::check_verifiable,
-    state::{ReceiveConfig, UlnConfig},
-    ID as ULN_ID, RECEIVE_CONFIG_SEED,
-};
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub enum VerificationState {
@@ -22,9 +17,26 @@ pub enum VerificationState {
     VerifiableButCapExceeded, // verifiable but not enough pending nonce space
 }
 
+/// The verification state of a packet with any of the message libs of the dvn, which implement
+/// the verifiable interface. The remaining accounts are passed to the receive library as it lays
+/// them out, e.g. the receive configs, the dvn set and the confirmation accounts for the uln.
 #[derive(Accounts)]
 #[instruction(params: VerifiableParams)]
 pub struct Verifiable<'info> {
+    #[account(seeds = [DVN_CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, DvnConfig>,
+    /// CHECK: the program of the message lib, called through the verifiable interface
+    #[account(executable)]
+    pub receive_library_program: UncheckedAccount<'info>,
+    /// CHECK: the message lib account of the receive library program
+    #[account(
+        owner = receive_library_program.key(),
+        constraint = config.is_msglib_allowed(&message_lib.key()) @DvnError::MsgLibNotAllowed
+    )]
+    pub message_lib: UncheckedAccount<'info>,
+    /// CHECK: the endpoint program of the message lib
+    #[account(executable)]
+    pub endpoint_program: UncheckedAccount<'info>,
     #[account(
         seeds = [
             NONCE_SEED,
@@ -33,7 +45,7 @@ pub struct Verifiable<'info> {
             &packet_v1_codec::sender(&params.packet_header)[..]
         ],
         bump,
-        seeds::program = ENDPOINT_ID
+        seeds::program = endpoint_program.key()
     )]
     pub nonce: AccountInfo<'info>, // deserialize only if exists (possibly not initializable)
     #[account(
@@ -45,37 +57,22 @@ pub struct Verifiable<'info> {
             &packet_v1_codec::nonce(&params.packet_header).to_be_bytes()
         ],
         bump,
-        seeds::program = ENDPOINT_ID
+        seeds::program = endpoint_program.key()
     )]
     pub payload_hash: AccountInfo<'info>, // deserialize only if exists
-    #[account(
-        seeds = [
-            RECEIVE_CONFIG_SEED,
-            &packet_v1_codec::src_eid(&params.packet_header).to_be_bytes(),
-            packet_v1_codec::receiver(&params.packet_header).as_ref()
-        ],
-        bump,
-        seeds::program = ULN_ID
-    )]
-    pub receive_config: AccountInfo<'info>, // deserialize only if configured
-    #[account(
-        seeds = [RECEIVE_CONFIG_SEED, &packet_v1_codec::src_eid(&params.packet_header).to_be_bytes()],
-        bump = default_receive_config.bump,
-        seeds::program = ULN_ID
-    )]
-    pub default_receive_config: Account<'info, ReceiveConfig>,
 }
 
-impl Verifiable<'_> {
-    pub fn apply(
-        ctx: &Context<Verifiable>,
+impl<'info> Verifiable<'info> {
+    pub fn apply<'c: 'info>(
+        ctx: &Context<'_, '_, 'c, 'info, Verifiable<'info>>,
         params: &VerifiableParams,
     ) -> Result<VerificationState> {
         // skip assert packet header, assume always correct
         let new_inbound_nonce = packet_v1_codec::nonce(&params.packet_header);
 
         // check endpoint initializable
-        let nonce = initializable(&ctx.accounts.nonce)?;
+        let endpoint_program = ctx.accounts.endpoint_program.key();
+        let nonce = initializable(&ctx.accounts.nonce, &endpoint_program)?;
         if nonce.is_none() {
             return Ok(VerificationState::NotInitializable);
         }
@@ -96,16 +93,23 @@ impl Verifiable<'_> {
             return Ok(VerificationState::VerifiableButCapExceeded);
         }
 
-        // check uln verifiable
-        if check_verifiable(
-            &get_receive_config(
-                &ctx.accounts.receive_config,
-                &ctx.accounts.default_receive_config,
-            )?,
-            ctx.remaining_accounts, // confirmation accounts
-            &keccak::hash(&params.packet_header).to_bytes(),
-            &params.payload_hash,
-        )? {
+        // check message lib verifiable
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.receive_library_program.to_account_info(),
+            messagelib_interface::cpi::accounts::VerifiableInterface {
+                message_lib: ctx.accounts.message_lib.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        let verifiable = messagelib_interface::cpi::verifiable(
+            cpi_ctx,
+            messagelib_interface::VerifiableParams {
+                packet_header: params.packet_header.to_vec(),
+                payload_hash: params.payload_hash,
+            },
+        )?
+        .get();
+        if verifiable {
             return Ok(VerificationState::Verifiable);
         }
 
@@ -113,24 +117,9 @@ impl Verifiable<'_> {
     }
 }
 
-fn get_receive_config(
-    receive_config_acc: &AccountInfo,
-    default_receive_config: &Account<ReceiveConfig>,
-) -> Result<UlnConfig> {
-    let custom_cfg = if receive_config_acc.owner.key() == ULN_ID {
-        let mut data: &[u8] = &receive_config_acc.try_borrow_data()?;
-        ReceiveConfig::try_deserialize(&mut data)?
-    } else {
-        // using default
-        ReceiveConfig::default()
-    };
-
-    UlnConfig::get_config(&default_receive_config.uln, &custom_cfg.uln)
-}
-
 // returns None if not initializable
-fn initializable(nonce_acc: &AccountInfo) -> Result<Option<Nonce>> {
-    if nonce_acc.owner.key() == ENDPOINT_ID {
+fn initializable(nonce_acc: &AccountInfo, endpoint_program: &Pubkey) -> Result<Option<Nonce>> {
+    if nonce_acc.owner == endpoint_program {
         let mut data: &[u8] = &nonce_acc.try_borrow_data()?;
         Ok(Some(Nonce::try_deserialize(&mut data)?))
     } else {
@@ -146,7 +135,7 @@ fn endpoint_verifiable(
 ) -> Result<bool> {
     // skip check for valid receive library, assume always correct
     // skip empty payload hash, assume always correct
-    if ctx.accounts.payload_hash.owner.key() == ENDPOINT_ID {
+    if ctx.accounts.payload_hash.owner.key() == ctx.accounts.endpoint_program.key() {
         // 1. verified if same payload hash
         let mut data: &[u8] = &ctx.accounts.payload_hash.try_borrow_data()?;
         if *hash == PayloadHash::try_deserialize(&mut data)?.hash {
@@ -0,0 +1,101 @@
+use crate::*;
+use anchor_lang::{
+    solana_program::{instruction::Instruction, keccak, program::invoke_signed, sysvar},
+    InstructionData,
+};
+use messagelib_helper::packet_v1_codec::PACKET_HEADER_SIZE;
+use uln::{program::Uln, state::UlnSettings, ULN_SEED};
+use utils::ed25519::verified_ed25519_signatures;
+
+/// Verifies the packet with the uln for the dvn, signed by the quorum of the signers. Unlike
+/// invoke(), it can be submitted by anyone and does not create an execute hash account, as
+/// verifying a packet again with the same confirmations does not change the verification.
+#[derive(Accounts)]
+pub struct Verify<'info> {
+    #[account(seeds = [DVN_CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, DvnConfig>,
+    #[account(
+        seeds = [ULN_SEED],
+        bump = uln.bump,
+        seeds::program = uln_program.key(),
+        constraint = config.is_msglib_allowed(&uln.key()) @DvnError::MsgLibNotAllowed
+    )]
+    pub uln: Account<'info, UlnSettings>,
+    /// CHECK: the confirmations account of the dvn, asserted by the uln
+    #[account(mut)]
+    pub confirmations: UncheckedAccount<'info>,
+    pub uln_program: Program<'info, Uln>,
+    /// The authority for the uln program to emit events
+    pub uln_event_authority: UncheckedAccount<'info>,
+    /// CHECK: the instructions sysvar to introspect the ed25519 signatures
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+impl Verify<'_> {
+    pub fn apply(ctx: &mut Context<Verify>, params: &VerifyParams) -> Result<()> {
+        require!(!ctx.accounts.config.paused, DvnError::Paused);
+
+        // verify signatures
+        let digest = VerifyDigest {
+            vid: ctx.accounts.config.vid,
+            msglib: ctx.accounts.uln.key(),
+            packet_header: params.packet_header,
+            payload_hash: params.payload_hash,
+            confirmations: params.confirmations,
+        };
+        let hash = keccak::hash(&digest.try_to_vec()?).to_bytes();
+        let ed25519_signatures = verified_ed25519_signatures(&ctx.accounts.instructions_sysvar)?;
+        ctx.accounts
+            .config
+            .verify_signatures(&params.signatures, &ed25519_signatures, &hash)?;
+
+        // the dvn config is the dvn signer to the uln
+        let accounts = uln::accounts::Verify {
+            dvn: ctx.accounts.config.key(),
+            confirmations: ctx.accounts.confirmations.key(),
+            event_authority: ctx.accounts.uln_event_authority.key(),
+            program: ctx.accounts.uln_program.key(),
+        };
+        let data = uln::instruction::Verify {
+            params: uln::instructions::VerifyParams {
+                packet_header: params.packet_header,
+                payload_hash: params.payload_hash,
+                confirmations: params.confirmations,
+            },
+        };
+        invoke_signed(
+            &Instruction {
+                program_id: ctx.accounts.uln_program.key(),
+                accounts: accounts.to_account_metas(None),
+                data: data.data(),
+            },
+            &[
+                ctx.accounts.config.to_account_info(),
+                ctx.accounts.confirmations.to_account_info(),
+                ctx.accounts.uln_event_authority.to_account_info(),
+                ctx.accounts.uln_program.to_account_info(),
+            ],
+            &[&[DVN_CONFIG_SEED, &[ctx.accounts.config.bump]]],
+        )?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct VerifyParams {
+    pub packet_header: [u8; PACKET_HEADER_SIZE],
+    pub payload_hash: [u8; 32],
+    pub confirmations: u64,
+    pub signatures: Vec<[u8; SIGNATURE_RAW_BYTES]>,
+}
+
+/// The digest signed by the signers to verify the packet
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct VerifyDigest {
+    pub vid: u32,
+    pub msglib: Pubkey,
+    pub packet_header: [u8; PACKET_HEADER_SIZE],
+    pub payload_hash: [u8; 32],
+    pub confirmations: u64,
+}
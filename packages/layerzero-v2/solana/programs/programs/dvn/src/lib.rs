@@ -1,15 +1,18 @@
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod options_codec;
 pub mod state;
 
 use anchor_lang::prelude::*;
 use errors::*;
 use events::*;
 use instructions::*;
+use options_codec::*;
 use solana_helper::program_id_from_env;
 use state::*;
-use worker_interface::QuoteDvnParams;
+use uln::state::EidFee;
+use worker_interface::{QuoteDvnParams, TokenFee};
 
 declare_id!(Pubkey::new_from_array(program_id_from_env!(
     "DVN_ID",
@@ -18,6 +21,8 @@ declare_id!(Pubkey::new_from_array(program_id_from_env!(
 
 pub const DVN_CONFIG_SEED: &[u8] = b"DvnConfig";
 pub const EXECUTE_HASH_SEED: &[u8] = b"ExecuteHash";
+pub const OAPP_PRICING_SEED: &[u8] = b"OAppPricing";
+pub const ACL_PAGE_SEED: &[u8] = b"AclPage";
 
 #[program]
 pub mod dvn {
@@ -36,7 +41,25 @@ pub mod dvn {
         ExtendDVNConfig::apply(&mut ctx)
     }
 
-    pub fn invoke(mut ctx: Context<Invoke>, params: InvokeParams) -> Result<()> {
+    pub fn migrate_dvn_config(mut ctx: Context<MigrateDvnConfig>) -> Result<()> {
+        MigrateDvnConfig::apply(&mut ctx)
+    }
+
+    pub fn set_oapp_pricing(
+        mut ctx: Context<SetOAppPricing>,
+        params: SetOAppPricingParams,
+    ) -> Result<()> {
+        SetOAppPricing::apply(&mut ctx, &params)
+    }
+
+    pub fn init_acl_page(mut ctx: Context<InitAclPage>, params: InitAclPageParams) -> Result<()> {
+        InitAclPage::apply(&mut ctx, &params)
+    }
+
+    pub fn invoke<'c: 'info, 'info>(
+        mut ctx: Context<'_, '_, 'c, 'info, Invoke<'info>>,
+        params: InvokeParams,
+    ) -> Result<()> {
         Invoke::apply(&mut ctx, &params)
     }
 
@@ -44,17 +67,42 @@ pub mod dvn {
         CloseExecute::apply(&mut ctx, &params)
     }
 
+    pub fn close_expired_executes<'c: 'info, 'info>(
+        mut ctx: Context<'_, '_, 'c, 'info, CloseExpiredExecutes<'info>>,
+    ) -> Result<()> {
+        CloseExpiredExecutes::apply(&mut ctx)
+    }
+
     pub fn withdraw_fee(mut ctx: Context<WithdrawFee>, params: WithdrawFeeParams) -> Result<()> {
         WithdrawFee::apply(&mut ctx, &params)
     }
 
+    /// --------------------------- Verifier Instructions ---------------------------
+    pub fn verify(mut ctx: Context<Verify>, params: VerifyParams) -> Result<()> {
+        Verify::apply(&mut ctx, &params)
+    }
+
     /// --------------------------- MsgLib Instructions ---------------------------
-    pub fn quote_dvn(ctx: Context<Quote>, params: QuoteDvnParams) -> Result<u64> {
+    pub fn quote_dvn<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, Quote<'info>>,
+        params: QuoteDvnParams,
+    ) -> Result<u64> {
         Quote::apply(&ctx, &params)
     }
 
-    pub fn verifiable(
-        ctx: Context<Verifiable>,
+    pub fn quote_dvn_in_token<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, Quote<'info>>,
+        params: QuoteDvnParams,
+    ) -> Result<Option<TokenFee>> {
+        Quote::apply_in_token(&ctx, &params)
+    }
+
+    pub fn fee_stats(ctx: Context<GetFeeStats>) -> Result<Vec<EidFee>> {
+        GetFeeStats::apply(&ctx)
+    }
+
+    pub fn verifiable<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, Verifiable<'info>>,
         params: VerifiableParams,
     ) -> Result<VerificationState> {
         Verifiable::apply(&ctx, &params)
@@ -0,0 +1,12 @@
+use crate::*;
+use utils::bytes_lib::BytesUtils;
+
+pub const OPTION_TYPE_COMPUTE: u8 = 2;
+
+/// The declared compute units of the request and the size of its response in bytes
+pub fn decode_compute_params(params: &[u8]) -> Result<(u64, u32)> {
+    require!(params.len() == 12, DvnError::InvalidOptionSize);
+    let compute_units = params.to_u64(0);
+    let response_size = params.to_u32(8);
+    Ok((compute_units, response_size))
+}
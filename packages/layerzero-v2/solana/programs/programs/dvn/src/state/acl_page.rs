@@ -0,0 +1,99 @@
+use crate::*;
+use worker_interface::worker_utils::insert_or_remove_sorted_pubkey_list;
+
+pub const ACL_PAGE_LEN: usize = 64;
+pub const PAGED_ACL_MAX_LEN: usize = 16;
+
+/// A page of the allowlist of a destination, set by quorum. When a destination has a paged
+/// allowlist, it takes the place of the allowlist of the dvn config for the destination.
+#[account]
+#[derive(InitSpace)]
+pub struct AclPage {
+    pub dst_eid: u32,
+    pub page: u8,
+    pub bump: u8,
+    #[max_len(ACL_PAGE_LEN)]
+    pub allow_list: Vec<Pubkey>, // sorted
+}
+
+impl AclPage {
+    pub fn set_allowlist(&mut self, oapp: &Pubkey) -> Result<()> {
+        insert_or_remove_sorted_pubkey_list(&mut self.allow_list, ACL_PAGE_LEN, oapp)
+    }
+
+    pub fn contains(&self, oapp: &Pubkey) -> bool {
+        self.allow_list.binary_search(oapp).is_ok()
+    }
+}
+
+/// The destinations with a paged allowlist and the number of oapps on their pages
+#[derive(InitSpace, Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct PagedAcl {
+    #[max_len(PAGED_ACL_MAX_LEN)]
+    pub eids: Vec<PagedAclEid>, // sorted by eid
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PagedAclEid {
+    pub eid: u32,
+    pub entries: u32,
+}
+
+impl PagedAcl {
+    pub fn is_paged(&self, eid: u32) -> bool {
+        self.eids.binary_search_by_key(&eid, |e| e.eid).is_ok()
+    }
+
+    /// Updates the number of oapps on the pages of the destination after a page of it changed
+    /// from `old_len` to `new_len` oapps. The destination is removed when its pages are empty.
+    pub fn update(&mut self, eid: u32, old_len: usize, new_len: usize) -> Result<()> {
+        match self.eids.binary_search_by_key(&eid, |e| e.eid) {
+            Ok(index) => {
+                let entries = self.eids[index].entries as usize + new_len - old_len;
+                if entries == 0 {
+                    self.eids.remove(index);
+                } else {
+                    self.eids[index].entries = entries as u32;
+                }
+            },
+            Err(index) => {
+                if new_len > 0 {
+                    require!(self.eids.len() < PAGED_ACL_MAX_LEN, DvnError::TooManyPagedAcls);
+                    self.eids.insert(index, PagedAclEid { eid, entries: new_len as u32 });
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+utils::generate_account_size_test!(AclPage, acl_page_test);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paged_acl_update() {
+        let mut paged_acl = PagedAcl::default();
+        paged_acl.update(2, 0, 0).unwrap();
+        assert!(!paged_acl.is_paged(2));
+
+        paged_acl.update(2, 0, 3).unwrap();
+        paged_acl.update(1, 0, 1).unwrap();
+        paged_acl.update(2, 3, 4).unwrap();
+        assert_eq!(
+            paged_acl.eids.iter().map(|e| (e.eid, e.entries)).collect::<Vec<_>>(),
+            vec![(1, 1), (2, 4)]
+        );
+
+        paged_acl.update(2, 4, 0).unwrap();
+        assert!(!paged_acl.is_paged(2));
+        assert!(paged_acl.is_paged(1));
+
+        for eid in 2..=PAGED_ACL_MAX_LEN as u32 {
+            paged_acl.update(eid, 0, 1).unwrap();
+        }
+        assert!(paged_acl.update(100, 0, 1).is_err());
+    }
+}
@@ -3,7 +3,11 @@ use anchor_lang::solana_program::secp256k1_recover::{
     secp256k1_recover, SECP256K1_PUBLIC_KEY_LENGTH,
 };
 use std::collections::HashSet;
-use utils::sorted_list_helper;
+use uln::state::LedgerFees;
+use utils::{
+    appended_field::{Appended, AppendedFields},
+    sorted_list_helper,
+};
 use worker_interface::worker_utils::{self, insert_or_remove_sorted_pubkey_list};
 
 /// encoded: funcSigHash + params -> 4  + (32 * 2)
@@ -21,6 +25,11 @@ pub const SIGNERS_MAX_LEN: usize = 7;
 pub const MSGLIBS_MAX_LEN: usize = 10;
 pub const DST_CONFIG_DEFAULT_LEN: usize = 140;
 pub const DST_CONFIG_MAX_LEN: usize = 200;
+/// The space added to the dvn config by extend_dvn_config() for the dst configs over the default
+pub const DST_CONFIG_EXTENDED_SPACE: usize =
+    DstConfig::INIT_SPACE * (DST_CONFIG_MAX_LEN - DST_CONFIG_DEFAULT_LEN);
+pub const DST_COMPUTE_CONFIG_MAX_LEN: usize = 32;
+pub const SIGNER_OVERLAP_MAX_SECONDS: u32 = 7 * 24 * 60 * 60;
 
 #[account]
 #[derive(InitSpace)]
@@ -43,21 +52,156 @@ pub struct DvnConfig {
     #[max_len(DST_CONFIG_DEFAULT_LEN)]
     pub dst_configs: Vec<DstConfig>,
     pub default_multiplier_bps: u16,
+    pub appended: Appended<DvnConfigAppended>,
+}
+
+/// The fields appended to the dvn config after the accounts were created. They are read as the
+/// defaults until the account is migrated by migrate_dvn_config().
+#[derive(InitSpace, Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct DvnConfigAppended {
+    // set by admins
+    pub fee_token: Option<worker_utils::FeeToken>,
+    // set by quorum, counted towards the quorum of the multisig
+    #[max_len(SIGNERS_MAX_LEN)]
+    pub ed25519_signers: Vec<Pubkey>,
+    // set by quorum
+    pub paged_acl: PagedAcl,
+    // set by quorum, the signers removed by the last rotation still counted until the expiration
+    pub outgoing_signers: OutgoingSigners,
+    // set by admins
+    #[max_len(DST_COMPUTE_CONFIG_MAX_LEN)]
+    pub dst_compute_configs: Vec<DstComputeConfig>, // sorted by eid
+    // set on withdrawal, the fees of the fee ledgers attributed to the withdrawals so far
+    pub withdrawn_fees: LedgerFees,
+}
+
+impl AppendedFields for DvnConfigAppended {
+    const DISCRIMINATOR: [u8; 8] = *b"DvnCfgEx";
+    const VERSION: u8 = 1;
 }
 
 impl DvnConfig {
-    pub fn set_multisig(&mut self, multisig: Multisig) -> Result<()> {
-        multisig.sanity_check()?;
+    pub fn set_signers(&mut self, signers: &[SignerKey], quorum: u8) -> Result<()> {
+        let mut multisig = Multisig { signers: Vec::new(), quorum };
+        let mut ed25519_signers = Vec::new();
+        for signer in signers {
+            match signer {
+                SignerKey::Secp256k1(pubkey) => multisig.signers.push(*pubkey),
+                SignerKey::Ed25519(pubkey) => ed25519_signers.push(*pubkey),
+            }
+        }
+        multisig.sanity_check(&ed25519_signers)?;
         self.multisig = multisig;
+        match self.appended.get_mut() {
+            Some(appended) => {
+                appended.ed25519_signers = ed25519_signers;
+                // the signers set directly end the overlap of the last rotation
+                appended.outgoing_signers = OutgoingSigners::default();
+            },
+            None => require!(ed25519_signers.is_empty(), DvnError::NotMigrated),
+        }
+        Ok(())
+    }
+
+    pub fn signers(&self) -> Vec<SignerKey> {
+        let secp256k1_signers = self.multisig.signers.iter().map(|s| SignerKey::Secp256k1(*s));
+        let ed25519_signers = self.appended.ed25519_signers.iter().map(|s| SignerKey::Ed25519(*s));
+        secp256k1_signers.chain(ed25519_signers).collect()
+    }
+
+    /// Replaces the removed signers with the added signers and sets the quorum at once, so the
+    /// quorum is checked against the new signers. The removed signers are still counted towards
+    /// the quorum for the overlap seconds, and a rotation without overlap ends the overlap of the
+    /// previous rotation.
+    pub fn rotate_signers(
+        &mut self,
+        add: &[SignerKey],
+        remove: &[SignerKey],
+        new_quorum: u8,
+        overlap_seconds: Option<u32>,
+        now: i64,
+    ) -> Result<()> {
+        let mut signers = self.signers();
+        for signer in remove {
+            let index =
+                signers.iter().position(|s| s == signer).ok_or(DvnError::SignerNotInCommittee)?;
+            signers.remove(index);
+        }
+        signers.extend_from_slice(add);
+        self.set_signers(&signers, new_quorum)?;
+
+        let outgoing_signers = match overlap_seconds {
+            Some(seconds) if seconds > 0 && !remove.is_empty() => {
+                require!(seconds <= SIGNER_OVERLAP_MAX_SECONDS, DvnError::InvalidSignerOverlap);
+                OutgoingSigners { signers: remove.to_vec(), expiration: now + seconds as i64 }
+            },
+            _ => OutgoingSigners::default(),
+        };
+        match self.appended.get_mut() {
+            Some(appended) => appended.outgoing_signers = outgoing_signers,
+            None => require!(outgoing_signers.signers.is_empty(), DvnError::NotMigrated),
+        }
         Ok(())
     }
 
+    /// Verifies the quorum of the signers signed the hash. The ed25519 signatures are the signers
+    /// and messages verified by the ed25519 program in the transaction, and the secp256k1
+    /// signatures are verified for the rest of the quorum.
+    pub fn verify_signatures(
+        &self,
+        sigs: &Vec<[u8; SIGNATURE_RAW_BYTES]>,
+        ed25519_signatures: &[(Pubkey, Vec<u8>)],
+        hash: &[u8; 32],
+    ) -> Result<()> {
+        let now = if self.appended.outgoing_signers.signers.is_empty() {
+            0
+        } else {
+            Clock::get()?.unix_timestamp
+        };
+        self.verify_signatures_at(sigs, ed25519_signatures, hash, now)
+    }
+
+    /// Verifies the signatures at the timestamp, counting the outgoing signers before their
+    /// expiration
+    pub fn verify_signatures_at(
+        &self,
+        sigs: &Vec<[u8; SIGNATURE_RAW_BYTES]>,
+        ed25519_signatures: &[(Pubkey, Vec<u8>)],
+        hash: &[u8; 32],
+        now: i64,
+    ) -> Result<()> {
+        let mut multisig = self.multisig.clone();
+        let mut ed25519_signers = self.appended.ed25519_signers.clone();
+        let outgoing_signers = &self.appended.outgoing_signers;
+        if now < outgoing_signers.expiration {
+            for signer in &outgoing_signers.signers {
+                match signer {
+                    SignerKey::Secp256k1(pubkey) => multisig.signers.push(*pubkey),
+                    SignerKey::Ed25519(pubkey) => ed25519_signers.push(*pubkey),
+                }
+            }
+        }
+
+        let ed25519_signed = ed25519_signatures
+            .iter()
+            .filter(|(signer, message)| message[..] == hash[..] && ed25519_signers.contains(signer))
+            .map(|(signer, _)| signer)
+            .collect::<HashSet<_>>()
+            .len();
+        multisig.verify_remaining_signatures(sigs, hash, ed25519_signed)
+    }
+
     pub fn set_admins(&mut self, admins: Vec<Pubkey>) -> Result<()> {
         require!(admins.len() <= ADMINS_MAX_LEN, DvnError::TooManyAdmins);
         self.admins = admins;
         Ok(())
     }
 
+    /// An empty list of message libs allows any message lib.
+    pub fn is_msglib_allowed(&self, msglib: &Pubkey) -> bool {
+        self.msglibs.is_empty() || self.msglibs.binary_search(msglib).is_ok()
+    }
+
     pub fn set_msglibs(&mut self, msglibs: Vec<Pubkey>) -> Result<()> {
         for lib in &msglibs {
             insert_or_remove_sorted_pubkey_list(&mut self.msglibs, MSGLIBS_MAX_LEN, lib)?;
@@ -82,6 +226,70 @@ impl DvnConfig {
         }
         Ok(())
     }
+
+    /// Attributes the withdrawn amount to the destinations, in the order of their eids, up to the
+    /// fees claimed to each from the fee ledgers that are not attributed to an earlier withdrawal.
+    /// Returns the attributed part of the amount by destination, none if the account is not
+    /// migrated. The rest of the amount is the fees paid to the dvn config account directly.
+    pub fn attribute_withdrawal(&mut self, claimed: &[EidFee], amount: u64) -> Vec<EidFee> {
+        let mut attributed = Vec::new();
+        if let Some(appended) = self.appended.get_mut() {
+            let mut remaining = amount;
+            for fee in claimed {
+                let withdrawn = appended.withdrawn_fees.get(fee.eid);
+                let part = fee.amount.saturating_sub(withdrawn).min(remaining);
+                if part > 0 && appended.withdrawn_fees.add(fee.eid, part) {
+                    attributed.push(EidFee { eid: fee.eid, amount: part });
+                    remaining -= part;
+                }
+            }
+        }
+        attributed
+    }
+
+    /// The appended fields to be set, which requires the account to be migrated
+    pub fn appended_mut(&mut self) -> Result<&mut DvnConfigAppended> {
+        self.appended.get_mut().ok_or(DvnError::NotMigrated.into())
+    }
+
+    /// The max number of dst configs, more than the default if the account is extended by
+    /// extend_dvn_config()
+    pub fn dst_configs_max_len(account_size: usize) -> usize {
+        if account_size >= 8 + DvnConfig::INIT_SPACE + DST_CONFIG_EXTENDED_SPACE {
+            DST_CONFIG_MAX_LEN
+        } else {
+            DST_CONFIG_DEFAULT_LEN
+        }
+    }
+
+    pub fn set_dst_compute_configs(&mut self, configs: Vec<DstComputeConfig>) -> Result<()> {
+        let appended = self.appended_mut()?;
+        for config in &configs {
+            sorted_list_helper::insert_or_update_sorted_list_by_eid(
+                &mut appended.dst_compute_configs,
+                config.clone(),
+                DST_COMPUTE_CONFIG_MAX_LEN,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn remove_dst_compute_configs(&mut self, dst_eids: Vec<u32>) -> Result<()> {
+        let appended = self.appended_mut()?;
+        for eid in dst_eids {
+            sorted_list_helper::remove_from_sorted_list_by_eid(
+                &mut appended.dst_compute_configs,
+                eid,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl sorted_list_helper::EID for DstComputeConfig {
+    fn eid(&self) -> u32 {
+        self.eid
+    }
 }
 
 impl sorted_list_helper::EID for DstConfig {
@@ -98,6 +306,20 @@ pub struct DstConfig {
     pub floor_margin_usd: Option<u128>,
 }
 
+/// The pricing of the compute option of a destination, for the requests doing more work than
+/// verifying a packet, such as reads. The declared compute units and response bytes are charged
+/// as gas on top of the dst gas, and the response bytes as calldata.
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct DstComputeConfig {
+    pub eid: u32,
+    pub gas_per_compute_unit: u32,
+    pub gas_per_response_byte: u32,
+    pub max_compute_units: u64,
+    pub max_response_size: u32,
+}
+
+/// The secp256k1 signers and the quorum of the multisig, which also counts the ed25519 signers of
+/// the dvn config
 #[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct Multisig {
     #[max_len(SIGNERS_MAX_LEN)]
@@ -105,16 +327,42 @@ pub struct Multisig {
     pub quorum: u8,
 }
 
+/// The signers removed by the last rotation of the signers, which are counted towards the quorum
+/// until the expiration
+#[derive(InitSpace, Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct OutgoingSigners {
+    #[max_len(SIGNERS_MAX_LEN)]
+    pub signers: Vec<SignerKey>,
+    pub expiration: i64,
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Debug)]
+pub enum SignerKey {
+    Secp256k1([u8; SECP256K1_PUBLIC_KEY_LENGTH]),
+    Ed25519(Pubkey),
+}
+
 impl Multisig {
     pub fn verify_signatures(
         &self,
         sigs: &Vec<[u8; SIGNATURE_RAW_BYTES]>,
         hash: &[u8; 32],
     ) -> Result<()> {
-        require!(sigs.len() >= self.quorum as usize, DvnError::InvalidSignatureLen);
+        self.verify_remaining_signatures(sigs, hash, 0)
+    }
+
+    /// Verifies the secp256k1 signatures for the rest of the quorum after the `signed` signers
+    pub fn verify_remaining_signatures(
+        &self,
+        sigs: &Vec<[u8; SIGNATURE_RAW_BYTES]>,
+        hash: &[u8; 32],
+        signed: usize,
+    ) -> Result<()> {
+        let remaining = (self.quorum as usize).saturating_sub(signed);
+        require!(sigs.len() >= remaining, DvnError::InvalidSignatureLen);
 
         let mut signed: HashSet<[u8; 64]> = HashSet::new();
-        for i in 0..self.quorum as usize {
+        for i in 0..remaining {
             let sig = &sigs[i][..(SIGNATURE_RAW_BYTES - 1)];
             let recovery_id = sigs[i][SIGNATURE_RAW_BYTES - 1];
             let pubkey = secp256k1_recover(&hash[..], recovery_id, sig)
@@ -127,18 +375,16 @@ impl Multisig {
         Ok(())
     }
 
-    pub fn sanity_check(&self) -> Result<()> {
-        require!(
-            self.signers.len() > 0 && self.signers.len() <= SIGNERS_MAX_LEN,
-            DvnError::InvalidSignersLen
-        );
-        require!(
-            self.quorum > 0 && self.quorum as usize <= self.signers.len(),
-            DvnError::InvalidQuorum
-        );
+    pub fn sanity_check(&self, ed25519_signers: &[Pubkey]) -> Result<()> {
+        let signers_len = self.signers.len() + ed25519_signers.len();
+        require!(signers_len > 0 && signers_len <= SIGNERS_MAX_LEN, DvnError::InvalidSignersLen);
+        require!(self.quorum > 0 && self.quorum as usize <= signers_len, DvnError::InvalidQuorum);
         let mut unique = HashSet::new();
         for signer in &self.signers {
-            require!(unique.insert(signer), DvnError::UniqueOwners);
+            require!(unique.insert(&signer[..]), DvnError::UniqueOwners);
+        }
+        for signer in ed25519_signers {
+            require!(unique.insert(signer.as_ref()), DvnError::UniqueOwners);
         }
         Ok(())
     }
@@ -1,5 +1,9 @@
+pub mod acl_page;
 pub mod dvn_config;
 pub mod execute_hash;
+pub mod oapp_pricing;
 
+pub use acl_page::*;
 pub use dvn_config::*;
 pub use execute_hash::*;
+pub use oapp_pricing::*;
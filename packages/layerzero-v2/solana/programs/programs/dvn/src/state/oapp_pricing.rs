@@ -0,0 +1,15 @@
+use crate::*;
+
+/// The pricing of an oapp to a destination, set by admins. The quote of the oapp takes it before
+/// the dst config of the destination, and falls back to the dst config for the fields not set.
+#[account]
+#[derive(InitSpace)]
+pub struct OAppPricing {
+    pub oapp: Pubkey,
+    pub dst_eid: u32,
+    pub bump: u8,
+    pub multiplier_bps: Option<u16>,
+    pub floor_margin_usd: Option<u128>,
+}
+
+utils::generate_account_size_test!(OAppPricing, oapp_pricing_test);
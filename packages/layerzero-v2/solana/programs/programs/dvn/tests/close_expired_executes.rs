@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod test_close_expired_executes {
+    use anchor_lang::prelude::*;
+    use dvn::instructions::close_expired_executes;
+    use dvn::state::{AclPage, ExecuteHash};
+
+    // closing an account assigns its owner and reallocates its data in place, which writes the
+    // data length before the data and reads the original data length before the key, as the
+    // runtime lays them out
+    fn account_data<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        let mut buf = (data.len() as u64).to_le_bytes().to_vec();
+        buf.extend(data);
+        buf
+    }
+
+    #[test]
+    fn skips_accounts_that_are_not_execute_hashes() {
+        let now = 100;
+        let keys =
+            [Pubkey::default(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut bufs = vec![
+            account_data(&ExecuteHash { expiration: now, bump: 0 }),
+            account_data(&AclPage { dst_eid: 1, page: 0, bump: 0, allow_list: vec![] }),
+            account_data(&ExecuteHash { expiration: now + 1, bump: 0 }),
+        ];
+        let mut lamports = [10u64, 20, 30];
+        let owners = [dvn::ID; 3];
+        let accounts = keys[1..]
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(bufs.iter_mut())
+            .zip(owners.iter())
+            .map(|(((key, lamports), buf), owner)| {
+                AccountInfo::new(key, false, true, lamports, &mut buf[8..], owner, false, 0)
+            })
+            .collect::<Vec<_>>();
+        let receiver_key = Pubkey::new_unique();
+        let mut receiver_lamports = 0;
+        let mut receiver_data = vec![];
+        let system = Pubkey::default();
+        let receiver = AccountInfo::new(
+            &receiver_key,
+            false,
+            true,
+            &mut receiver_lamports,
+            &mut receiver_data,
+            &system,
+            false,
+            0,
+        );
+
+        close_expired_executes(&accounts, &receiver, now).unwrap();
+
+        // only the expired execute hash is closed
+        assert_eq!(receiver.lamports(), 10);
+        assert_eq!(accounts[0].lamports(), 0);
+        assert_eq!(accounts[0].owner, &system);
+        assert_eq!(accounts[1].lamports(), 20);
+        assert_eq!(accounts[2].lamports(), 30);
+    }
+}
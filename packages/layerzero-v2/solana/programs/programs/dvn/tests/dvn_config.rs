@@ -2,9 +2,15 @@
 mod test_dvn_config {
     use anchor_lang::prelude::Pubkey;
     use anchor_lang::solana_program::keccak;
-    use dvn::state::{DvnConfig, Multisig};
+    use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+    use dvn::state::{
+        DstComputeConfig, DstConfig, DvnConfig, DvnConfigAppended, Multisig, SignerKey,
+        DST_CONFIG_DEFAULT_LEN, DST_CONFIG_EXTENDED_SPACE, DST_CONFIG_MAX_LEN,
+    };
     use secp256k1::rand::rngs::OsRng;
     use secp256k1::{All, Message, Secp256k1, SecretKey};
+    use uln::state::EidFee;
+    use utils::appended_field::Appended;
     use worker_interface::worker_utils;
 
     struct TestFixture {
@@ -41,6 +47,7 @@ mod test_dvn_config {
                     msglibs: vec![],
                     dst_configs: vec![],
                     multisig: Multisig { quorum: 2, signers },
+                    appended: Appended::new(DvnConfigAppended::default()),
                 },
                 secrets,
                 secp,
@@ -123,4 +130,231 @@ mod test_dvn_config {
 
         assert!(fixture.dvn_config.multisig.verify_signatures(&signatures, digest).is_ok());
     }
+
+    #[test]
+    fn verify_signatures_with_ed25519_signers() {
+        let mut fixture = TestFixture::new();
+        let ed25519_signer = Pubkey::new_unique();
+        let mut signers = fixture.dvn_config.signers();
+        signers.push(SignerKey::Ed25519(ed25519_signer));
+        fixture.dvn_config.set_signers(&signers, 2).unwrap();
+        assert_eq!(fixture.dvn_config.signers(), signers);
+
+        let digest = &keccak::hash("Hello World!".as_bytes()).to_bytes();
+        let sig_1 = fixture.sign(digest, &fixture.secrets[0]);
+        let ed25519_signatures = vec![(ed25519_signer, digest.to_vec())];
+
+        // one secp256k1 signature and one ed25519 signature
+        assert!(fixture
+            .dvn_config
+            .verify_signatures(&vec![sig_1], &ed25519_signatures, digest)
+            .is_ok());
+
+        // the ed25519 signature of another message is not counted
+        let other_signatures = vec![(ed25519_signer, [0u8; 32].to_vec())];
+        let result = fixture.dvn_config.verify_signatures(&vec![sig_1], &other_signatures, digest);
+        assert!(result.unwrap_err().to_string().contains("InvalidSignatureLen"));
+
+        // the ed25519 signature of a signer not in the committee is not counted
+        let other_signatures = vec![(Pubkey::new_unique(), digest.to_vec())];
+        let result = fixture.dvn_config.verify_signatures(&vec![sig_1], &other_signatures, digest);
+        assert!(result.unwrap_err().to_string().contains("InvalidSignatureLen"));
+    }
+
+    #[test]
+    fn set_signers_fails_with_duplicated_ed25519_signers() {
+        let mut fixture = TestFixture::new();
+        let ed25519_signer = Pubkey::new_unique();
+        let signers = vec![SignerKey::Ed25519(ed25519_signer), SignerKey::Ed25519(ed25519_signer)];
+        let result = fixture.dvn_config.set_signers(&signers, 1);
+        assert!(result.unwrap_err().to_string().contains("UniqueOwners"));
+    }
+
+    #[test]
+    fn rotate_signers_with_overlap() {
+        let mut fixture = TestFixture::new();
+        let (new_secret_key, new_public_key) = fixture.secp.generate_keypair(&mut OsRng);
+        let mut new_signer = [0u8; 64];
+        new_signer.copy_from_slice(&new_public_key.serialize_uncompressed()[1..65]);
+        let old_signer = fixture.dvn_config.multisig.signers[0];
+
+        // the quorum is checked against the rotated signers
+        let result = fixture.dvn_config.rotate_signers(
+            &[],
+            &[SignerKey::Secp256k1(old_signer)],
+            2,
+            None,
+            100,
+        );
+        assert!(result.unwrap_err().to_string().contains("InvalidQuorum"));
+
+        fixture
+            .dvn_config
+            .rotate_signers(
+                &[SignerKey::Secp256k1(new_signer)],
+                &[SignerKey::Secp256k1(old_signer)],
+                2,
+                Some(60),
+                100,
+            )
+            .unwrap();
+        assert!(!fixture.dvn_config.multisig.signers.contains(&old_signer));
+        assert!(fixture.dvn_config.multisig.signers.contains(&new_signer));
+
+        let digest = &keccak::hash("Hello World!".as_bytes()).to_bytes();
+        let old_sig = fixture.sign(digest, &fixture.secrets[0]);
+        let sig = fixture.sign(digest, &fixture.secrets[1]);
+        let new_sig = fixture.sign(digest, &new_secret_key);
+
+        // the outgoing signer is counted during the overlap only
+        let signatures = vec![old_sig, sig];
+        assert!(fixture.dvn_config.verify_signatures_at(&signatures, &[], digest, 159).is_ok());
+        let result = fixture.dvn_config.verify_signatures_at(&signatures, &[], digest, 160);
+        assert!(result.unwrap_err().to_string().contains("SignerNotInCommittee"));
+        let signatures = vec![new_sig, sig];
+        assert!(fixture.dvn_config.verify_signatures_at(&signatures, &[], digest, 160).is_ok());
+
+        // a rotation without overlap ends the overlap
+        fixture.dvn_config.rotate_signers(&[], &[], 2, None, 100).unwrap();
+        let signatures = vec![old_sig, sig];
+        assert!(fixture.dvn_config.verify_signatures_at(&signatures, &[], digest, 100).is_err());
+
+        // setting the signers or the quorum directly ends the overlap too
+        let (old, new) = (SignerKey::Secp256k1(old_signer), SignerKey::Secp256k1(new_signer));
+        fixture
+            .dvn_config
+            .rotate_signers(&[old.clone()], &[new.clone()], 2, Some(60), 100)
+            .unwrap();
+        assert!(!fixture.dvn_config.appended.outgoing_signers.signers.is_empty());
+        let signers = fixture.dvn_config.signers();
+        fixture.dvn_config.set_signers(&signers, 2).unwrap();
+        assert!(fixture.dvn_config.appended.outgoing_signers.signers.is_empty());
+        let signatures = vec![new_sig, sig];
+        assert!(fixture.dvn_config.verify_signatures_at(&signatures, &[], digest, 100).is_err());
+
+        fixture.dvn_config.rotate_signers(&[new], &[old], 2, Some(60), 100).unwrap();
+        assert!(!fixture.dvn_config.appended.outgoing_signers.signers.is_empty());
+        let signers = fixture.dvn_config.signers();
+        fixture.dvn_config.set_signers(&signers, 1).unwrap();
+        assert!(fixture.dvn_config.appended.outgoing_signers.signers.is_empty());
+
+        // the removed signers must be in the committee
+        let result = fixture.dvn_config.rotate_signers(
+            &[],
+            &[SignerKey::Secp256k1(old_signer)],
+            1,
+            None,
+            100,
+        );
+        assert!(result.unwrap_err().to_string().contains("SignerNotInCommittee"));
+    }
+
+    #[test]
+    fn deserialize_not_migrated_dvn_config_with_stale_data() {
+        let mut fixture = TestFixture::new();
+        fixture.dvn_config.appended = Appended::default();
+        let dst_config = |eid| DstConfig {
+            eid,
+            dst_gas: u32::MAX,
+            multiplier_bps: Some(u16::MAX),
+            floor_margin_usd: Some(u128::MAX),
+        };
+
+        // the account data left by a longer list of dst configs
+        fixture.dvn_config.dst_configs = (1..=10).map(dst_config).collect();
+        let mut data = fixture.dvn_config.try_to_vec().unwrap();
+        fixture.dvn_config.dst_configs = (1..=2).map(dst_config).collect();
+        let short_data = fixture.dvn_config.try_to_vec().unwrap();
+        data[..short_data.len()].copy_from_slice(&short_data);
+
+        let mut config = DvnConfig::deserialize(&mut &data[..]).unwrap();
+        assert_eq!(config.dst_configs.len(), 2);
+        assert!(!config.appended.is_migrated());
+        assert!(config.appended.fee_token.is_none());
+        assert!(config.appended.ed25519_signers.is_empty());
+        assert!(config.appended.outgoing_signers.signers.is_empty());
+
+        // the appended fields are set after the migration
+        let compute_config = DstComputeConfig {
+            eid: 1,
+            gas_per_compute_unit: 1,
+            gas_per_response_byte: 1,
+            max_compute_units: 1,
+            max_response_size: 1,
+        };
+        let result = config.set_dst_compute_configs(vec![compute_config.clone()]);
+        assert!(result.unwrap_err().to_string().contains("NotMigrated"));
+        config.appended.migrate();
+        config.set_dst_compute_configs(vec![compute_config]).unwrap();
+        let data = config.try_to_vec().unwrap();
+        let config = DvnConfig::deserialize(&mut &data[..]).unwrap();
+        assert_eq!(config.appended.dst_compute_configs.len(), 1);
+    }
+
+    #[test]
+    fn set_ed25519_signers_requires_migration() {
+        let mut fixture = TestFixture::new();
+        fixture.dvn_config.appended = Appended::default();
+
+        // the secp256k1 signers are set without the migration
+        let signers = fixture.dvn_config.signers();
+        fixture.dvn_config.set_signers(&signers[..1], 1).unwrap();
+
+        let signers = vec![SignerKey::Ed25519(Pubkey::new_unique())];
+        let result = fixture.dvn_config.set_signers(&signers, 1);
+        assert!(result.unwrap_err().to_string().contains("NotMigrated"));
+
+        let result = fixture.dvn_config.rotate_signers(&signers, &[], 1, Some(60), 100);
+        assert!(result.unwrap_err().to_string().contains("NotMigrated"));
+    }
+
+    #[test]
+    fn msglibs_empty_allows_any() {
+        let mut fixture = TestFixture::new();
+        let msglib = Pubkey::new_unique();
+        assert!(fixture.dvn_config.is_msglib_allowed(&msglib));
+
+        let allowed = Pubkey::new_unique();
+        fixture.dvn_config.set_msglibs(vec![allowed]).unwrap();
+        assert!(fixture.dvn_config.is_msglib_allowed(&allowed));
+        assert!(!fixture.dvn_config.is_msglib_allowed(&msglib));
+
+        // removing the last message lib allows any again
+        fixture.dvn_config.set_msglibs(vec![allowed]).unwrap();
+        assert!(fixture.dvn_config.is_msglib_allowed(&msglib));
+    }
+
+    #[test]
+    fn attribute_withdrawal() {
+        let mut fixture = TestFixture::new();
+        let claimed = vec![EidFee { eid: 1, amount: 100 }, EidFee { eid: 2, amount: 50 }];
+
+        let fees = fixture.dvn_config.attribute_withdrawal(&claimed, 120);
+        assert_eq!(fees, vec![EidFee { eid: 1, amount: 100 }, EidFee { eid: 2, amount: 20 }]);
+
+        // only the fees claimed since are attributed, the rest is not attributed
+        let claimed = vec![EidFee { eid: 1, amount: 110 }, EidFee { eid: 2, amount: 50 }];
+        let fees = fixture.dvn_config.attribute_withdrawal(&claimed, 100);
+        assert_eq!(fees, vec![EidFee { eid: 1, amount: 10 }, EidFee { eid: 2, amount: 30 }]);
+        assert!(fixture.dvn_config.attribute_withdrawal(&claimed, 100).is_empty());
+
+        fixture.dvn_config.appended = Appended::default();
+        assert!(fixture.dvn_config.attribute_withdrawal(&claimed, 100).is_empty());
+    }
+
+    #[test]
+    fn dst_configs_max_len() {
+        use anchor_lang::Space;
+        let migrated = 8 + DvnConfig::INIT_SPACE;
+        let not_migrated = migrated - Appended::<DvnConfigAppended>::INIT_SPACE;
+        assert_eq!(DvnConfig::dst_configs_max_len(not_migrated), DST_CONFIG_DEFAULT_LEN);
+        assert_eq!(DvnConfig::dst_configs_max_len(migrated), DST_CONFIG_DEFAULT_LEN);
+        // an account extended before the migration holds the appended fields once migrated
+        let extended = not_migrated + DST_CONFIG_EXTENDED_SPACE;
+        assert_eq!(DvnConfig::dst_configs_max_len(extended), DST_CONFIG_DEFAULT_LEN);
+        assert_eq!(
+            DvnConfig::dst_configs_max_len(extended + Appended::<DvnConfigAppended>::INIT_SPACE),
+            DST_CONFIG_MAX_LEN
+        );
+    }
 }
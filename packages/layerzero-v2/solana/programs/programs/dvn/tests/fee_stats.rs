@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod test_fee_stats {
+    use anchor_lang::prelude::*;
+    use dvn::instructions::{claimed_fees, sum_fee_ledgers};
+    use uln::state::{ClaimedFees, EidFee, FeeLedger, LedgerFees};
+
+    fn fee_ledger_data(worker: Pubkey, shard: u8, fees: Vec<EidFee>) -> Vec<u8> {
+        let fee_ledger = FeeLedger {
+            worker,
+            shard,
+            bump: 0,
+            fees: LedgerFees { fees },
+            claimed_fees: LedgerFees::default(),
+        };
+        let mut data = vec![];
+        fee_ledger.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn sum_fee_ledgers_of_the_uln() {
+        let dvn = Pubkey::new_unique();
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut lamports = [0u64; 3];
+        let mut data = vec![
+            fee_ledger_data(dvn, 0, vec![EidFee { eid: 1, amount: 10 }]),
+            fee_ledger_data(
+                dvn,
+                1,
+                vec![EidFee { eid: 1, amount: 5 }, EidFee { eid: 2, amount: 7 }],
+            ),
+            // a fee ledger of the dvn by its layout, but not owned by the uln
+            fee_ledger_data(dvn, 2, vec![EidFee { eid: 2, amount: 1000 }]),
+        ];
+        let other_program = Pubkey::new_unique();
+        let owners = [uln::ID, uln::ID, other_program];
+        let accounts = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .zip(owners.iter())
+            .map(|(((key, lamports), data), owner)| {
+                AccountInfo::new(key, false, false, lamports, data, owner, false, 0)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            sum_fee_ledgers(&dvn, &accounts[..2]).unwrap(),
+            vec![EidFee { eid: 1, amount: 15 }, EidFee { eid: 2, amount: 7 }]
+        );
+
+        let result = sum_fee_ledgers(&dvn, &accounts);
+        assert!(result.unwrap_err().to_string().contains("InvalidFeeLedger"));
+
+        // the fee ledgers of another worker
+        let result = sum_fee_ledgers(&Pubkey::new_unique(), &accounts[..1]);
+        assert!(result.unwrap_err().to_string().contains("InvalidFeeLedger"));
+    }
+
+    #[test]
+    fn read_claimed_fees_of_the_uln() {
+        let (key, dvn) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let fees = vec![EidFee { eid: 1, amount: 10 }, EidFee { eid: 2, amount: 7 }];
+        let claimed = ClaimedFees { worker: dvn, bump: 0, fees: LedgerFees { fees: fees.clone() } };
+        let mut data = vec![];
+        claimed.try_serialize(&mut data).unwrap();
+
+        let mut lamports = 0;
+        let acc =
+            AccountInfo::new(&key, false, false, &mut lamports, &mut data, &uln::ID, false, 0);
+        assert_eq!(claimed_fees(&acc).unwrap(), fees);
+
+        // nothing is claimed before the account is created by the uln
+        let (mut lamports, mut data) = (0, vec![]);
+        let system = Pubkey::default();
+        let acc = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &system, false, 0);
+        assert!(claimed_fees(&acc).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod test_options_codec {
+    use dvn::options_codec::decode_compute_params;
+
+    #[test]
+    fn test_decode_compute_params() {
+        let mut params = 1_000_000u64.to_be_bytes().to_vec();
+        params.extend_from_slice(&512u32.to_be_bytes());
+        assert_eq!(decode_compute_params(&params).unwrap(), (1_000_000, 512));
+
+        let params = [u64::MAX.to_be_bytes().to_vec(), u32::MAX.to_be_bytes().to_vec()].concat();
+        assert_eq!(decode_compute_params(&params).unwrap(), (u64::MAX, u32::MAX));
+    }
+
+    #[test]
+    fn test_decode_compute_params_with_invalid_size() {
+        for len in [0, 8, 11, 13] {
+            let result = decode_compute_params(&vec![0; len]);
+            assert!(result.unwrap_err().to_string().contains("InvalidOptionSize"));
+        }
+    }
+}
@@ -0,0 +1,294 @@
+#[cfg(test)]
+mod test_quote {
+    use anchor_lang::prelude::*;
+    use anchor_lang::solana_program::{
+        entrypoint::ProgramResult, instruction::Instruction, program_stubs,
+    };
+    use dvn::errors::DvnError;
+    use dvn::options_codec::OPTION_TYPE_COMPUTE;
+    use dvn::state::{
+        AclPage, DstComputeConfig, DstConfig, DvnConfig, DvnConfigAppended, Multisig, OAppPricing,
+        PagedAclEid,
+    };
+    use dvn::{ACL_PAGE_SEED, DVN_CONFIG_SEED, OAPP_PRICING_SEED};
+    use messagelib_helper::messagelib_interface::Packet;
+    use pricefeed::instructions::GetFeeParams;
+    use std::cell::RefCell;
+    use uln::instructions::quote_dvns;
+    use uln::options_codec::DVNOptions;
+    use uln::state::UlnConfig;
+    use utils::appended_field::Appended;
+    use worker_interface::{worker_utils, LzOption, WorkerError};
+
+    const DST_EID: u32 = 2;
+    const PAGED_DST_EID: u32 = 3;
+    const PRICE_FEED_FEE_PER_GAS: u128 = 1000;
+
+    thread_local! {
+        static RETURN_DATA: RefCell<Option<(Pubkey, Vec<u8>)>> = RefCell::new(None);
+        static GET_FEE_PARAMS: RefCell<Option<GetFeeParams>> = RefCell::new(None);
+    }
+
+    /// Runs the dvn program for the cpis of the uln to it, and returns the fee of the price feed
+    /// by the total gas for the cpis of the dvn to any other program.
+    struct DvnStubs;
+
+    impl program_stubs::SyscallStubs for DvnStubs {
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            if instruction.program_id != dvn::ID {
+                let params = GetFeeParams::deserialize(&mut &instruction.data[8..]).unwrap();
+                let fee = (params.total_gas * PRICE_FEED_FEE_PER_GAS, 0u128, 0u128, None::<u128>);
+                GET_FEE_PARAMS.with(|get_fee_params| *get_fee_params.borrow_mut() = Some(params));
+                RETURN_DATA.with(|data| {
+                    *data.borrow_mut() = Some((instruction.program_id, fee.try_to_vec().unwrap()))
+                });
+                return Ok(());
+            }
+            let accounts = instruction
+                .accounts
+                .iter()
+                .map(|meta| account_infos.iter().find(|info| info.key == &meta.pubkey).unwrap())
+                .cloned()
+                .collect::<Vec<_>>();
+            // the dvn program takes the accounts for the lifetime of the instruction
+            let accounts: &[AccountInfo] = unsafe { std::mem::transmute(accounts.as_slice()) };
+            dvn::entry(&instruction.program_id, accounts, &instruction.data)
+        }
+
+        fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+            RETURN_DATA.with(|data| data.borrow().clone())
+        }
+
+        fn sol_set_return_data(&self, data: &[u8]) {
+            RETURN_DATA
+                .with(|return_data| *return_data.borrow_mut() = Some((dvn::ID, data.to_vec())))
+        }
+    }
+
+    fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn dvn_config(bump: u8, price_feed: Pubkey) -> DvnConfig {
+        let mut appended = DvnConfigAppended::default();
+        appended.paged_acl.eids.push(PagedAclEid { eid: PAGED_DST_EID, entries: 1 });
+        appended.dst_compute_configs.push(DstComputeConfig {
+            eid: DST_EID,
+            gas_per_compute_unit: 2,
+            gas_per_response_byte: 3,
+            max_compute_units: 100,
+            max_response_size: 10,
+        });
+        DvnConfig {
+            vid: 0,
+            bump,
+            acl: worker_utils::Acl { allow_list: vec![], deny_list: vec![] },
+            default_multiplier_bps: 10000,
+            price_feed,
+            paused: false,
+            admins: vec![],
+            msglibs: vec![],
+            dst_configs: [DST_EID, PAGED_DST_EID]
+                .iter()
+                .map(|&eid| DstConfig {
+                    eid,
+                    dst_gas: 1,
+                    multiplier_bps: None,
+                    floor_margin_usd: None,
+                })
+                .collect(),
+            multisig: Multisig { quorum: 1, signers: vec![] },
+            appended: Appended::new(appended),
+        }
+    }
+
+    /// Quotes the dvn through the uln with the options and the dvn quote accounts, each given by
+    /// its address, its owner and its data.
+    fn quote_through_uln(
+        sender: Pubkey,
+        dst_eid: u32,
+        options: Vec<LzOption>,
+        dvn_quote_accounts: Vec<(Pubkey, Pubkey, Vec<u8>)>,
+    ) -> Result<u64> {
+        program_stubs::set_syscall_stubs(Box::new(DvnStubs));
+
+        let (config_key, bump) = Pubkey::find_program_address(&[DVN_CONFIG_SEED], &dvn::ID);
+        let (price_feed_program, price_feed_config) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let system = Pubkey::default();
+        let mut keys = vec![
+            (dvn::ID, system, true, vec![]),
+            (config_key, dvn::ID, false, serialize(&dvn_config(bump, price_feed_config))),
+            (price_feed_program, system, true, vec![]),
+            (price_feed_config, price_feed_program, false, vec![]),
+        ];
+        keys.extend(
+            dvn_quote_accounts
+                .into_iter()
+                .map(|(key, owner, data)| (key, owner, false, data)),
+        );
+        let mut lamports = vec![1u64; keys.len()];
+        let mut data = keys.iter().map(|(_, _, _, data)| data.clone()).collect::<Vec<_>>();
+        let accounts = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|(((key, owner, executable, _), lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, owner, *executable, 0)
+            })
+            .collect::<Vec<_>>();
+
+        let uln_config = UlnConfig {
+            confirmations: 1,
+            required_dvn_count: 1,
+            optional_dvn_count: 0,
+            optional_dvn_threshold: 0,
+            required_dvns: vec![config_key],
+            optional_dvns: vec![],
+        };
+        let packet = Packet {
+            nonce: 1,
+            src_eid: 1,
+            sender,
+            dst_eid,
+            receiver: [0u8; 32],
+            guid: [0u8; 32],
+            message: vec![],
+        };
+        let (fees, _) = quote_dvns(
+            &Pubkey::new_unique(),
+            &uln_config,
+            &packet,
+            DVNOptions::from([(0, options)]),
+            &accounts[..4],
+            &accounts[4..],
+            &[false],
+        )?;
+        Ok(fees[0].fee)
+    }
+
+    fn oapp_pricing(sender: Pubkey, multiplier_bps: u16) -> (Pubkey, Pubkey, Vec<u8>) {
+        let (key, bump) = Pubkey::find_program_address(
+            &[OAPP_PRICING_SEED, sender.as_ref(), &DST_EID.to_be_bytes()],
+            &dvn::ID,
+        );
+        let pricing = OAppPricing {
+            oapp: sender,
+            dst_eid: DST_EID,
+            bump,
+            multiplier_bps: Some(multiplier_bps),
+            floor_margin_usd: None,
+        };
+        (key, dvn::ID, serialize(&pricing))
+    }
+
+    fn acl_page(oapps: Vec<Pubkey>) -> (Pubkey, Pubkey, Vec<u8>) {
+        let (key, bump) = Pubkey::find_program_address(
+            &[ACL_PAGE_SEED, &PAGED_DST_EID.to_be_bytes(), &[0]],
+            &dvn::ID,
+        );
+        let page = AclPage { dst_eid: PAGED_DST_EID, page: 0, bump, allow_list: oapps };
+        (key, dvn::ID, serialize(&page))
+    }
+
+    #[test]
+    fn quote_with_oapp_pricing_forwarded_by_uln() {
+        let sender = Pubkey::new_unique();
+        assert_eq!(quote_through_uln(sender, DST_EID, vec![], vec![]).unwrap(), 1000);
+
+        let pricing = oapp_pricing(sender, 20000);
+        assert_eq!(
+            quote_through_uln(sender, DST_EID, vec![], vec![pricing.clone()]).unwrap(),
+            2000
+        );
+
+        // the oapp pricing is not taken at any other address
+        let (_, owner, data) = pricing;
+        let moved = (Pubkey::new_unique(), owner, data);
+        assert_eq!(quote_through_uln(sender, DST_EID, vec![], vec![moved]).unwrap(), 1000);
+
+        // the pricing of another oapp is not taken
+        let other = oapp_pricing(Pubkey::new_unique(), 20000);
+        assert_eq!(quote_through_uln(sender, DST_EID, vec![], vec![other]).unwrap(), 1000);
+    }
+
+    #[test]
+    fn quote_with_acl_page_forwarded_by_uln() {
+        let sender = Pubkey::new_unique();
+        let denied = ProgramError::from(Error::from(WorkerError::PermissionDenied));
+
+        let result = quote_through_uln(sender, PAGED_DST_EID, vec![], vec![]);
+        assert_eq!(ProgramError::from(result.unwrap_err()), denied);
+
+        let page = acl_page(vec![sender]);
+        assert_eq!(
+            quote_through_uln(sender, PAGED_DST_EID, vec![], vec![page.clone()]).unwrap(),
+            1000
+        );
+
+        // the acl page is not taken at any other address
+        let (_, owner, data) = page;
+        let moved = (Pubkey::new_unique(), owner, data);
+        let result = quote_through_uln(sender, PAGED_DST_EID, vec![], vec![moved]);
+        assert_eq!(ProgramError::from(result.unwrap_err()), denied);
+    }
+
+    fn compute_option(compute_units: u64, response_size: u32) -> LzOption {
+        let mut params = compute_units.to_be_bytes().to_vec();
+        params.extend_from_slice(&response_size.to_be_bytes());
+        LzOption { option_type: OPTION_TYPE_COMPUTE, params }
+    }
+
+    fn calldata_size() -> u64 {
+        GET_FEE_PARAMS.with(|params| params.borrow().as_ref().unwrap().calldata_size)
+    }
+
+    #[test]
+    fn quote_with_compute_option() {
+        let sender = Pubkey::new_unique();
+        assert_eq!(quote_through_uln(sender, DST_EID, vec![], vec![]).unwrap(), 1000);
+        let base_calldata_size = calldata_size();
+
+        // the dst gas of 1, 2 gas for each of the 10 compute units and 3 for each of the 4
+        // response bytes, which are charged as calldata too
+        let options = vec![compute_option(10, 4)];
+        assert_eq!(quote_through_uln(sender, DST_EID, options, vec![]).unwrap(), 33000);
+        assert_eq!(calldata_size(), base_calldata_size + 4);
+
+        // the compute options are summed up to the caps of the compute config
+        let options = vec![compute_option(60, 5), compute_option(40, 5)];
+        assert_eq!(quote_through_uln(sender, DST_EID, options, vec![]).unwrap(), 231000);
+
+        // the other option types are not priced
+        let options = vec![LzOption { option_type: 1, params: vec![] }];
+        assert_eq!(quote_through_uln(sender, DST_EID, options, vec![]).unwrap(), 1000);
+    }
+
+    #[test]
+    fn quote_with_compute_option_fails() {
+        let sender = Pubkey::new_unique();
+        let error = |e: DvnError| ProgramError::from(Error::from(e));
+
+        let options = vec![compute_option(101, 0)];
+        let result = quote_through_uln(sender, DST_EID, options, vec![]);
+        assert_eq!(ProgramError::from(result.unwrap_err()), error(DvnError::ComputeExceedsCap));
+
+        let options = vec![compute_option(60, 6), compute_option(0, 5)];
+        let result = quote_through_uln(sender, DST_EID, options, vec![]);
+        assert_eq!(ProgramError::from(result.unwrap_err()), error(DvnError::ComputeExceedsCap));
+
+        let options = vec![LzOption { option_type: OPTION_TYPE_COMPUTE, params: vec![0; 8] }];
+        let result = quote_through_uln(sender, DST_EID, options, vec![]);
+        assert_eq!(ProgramError::from(result.unwrap_err()), error(DvnError::InvalidOptionSize));
+
+        // the destination has no compute config
+        let options = vec![compute_option(1, 0)];
+        assert!(quote_through_uln(sender, PAGED_DST_EID, options, vec![]).is_err());
+    }
+}
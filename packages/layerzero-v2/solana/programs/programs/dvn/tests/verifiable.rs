@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod test_verifiable {
+    use anchor_lang::prelude::*;
+    use dvn::instructions::{Verifiable, VerifiableBumps, VerifiableParams};
+    use dvn::state::{DvnConfig, DvnConfigAppended, Multisig};
+    use dvn::DVN_CONFIG_SEED;
+    use messagelib_helper::endpoint::{NONCE_SEED, PAYLOAD_HASH_SEED};
+    use messagelib_helper::packet_v1_codec::{self, PACKET_HEADER_SIZE};
+    use std::collections::BTreeSet;
+    use utils::appended_field::Appended;
+    use worker_interface::worker_utils;
+
+    fn dvn_config(bump: u8, msglibs: Vec<Pubkey>) -> Vec<u8> {
+        let config = DvnConfig {
+            vid: 0,
+            bump,
+            acl: worker_utils::Acl { allow_list: vec![], deny_list: vec![] },
+            default_multiplier_bps: 0,
+            price_feed: Pubkey::new_unique(),
+            paused: false,
+            admins: vec![],
+            msglibs,
+            dst_configs: vec![],
+            multisig: Multisig { quorum: 1, signers: vec![] },
+            appended: Appended::new(DvnConfigAppended::default()),
+        };
+        let mut data = vec![];
+        config.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    // checks the account constraints of verifiable with the given msglibs of the dvn
+    fn try_verifiable_accounts(msglibs: Vec<Pubkey>, message_lib: Pubkey) -> Result<()> {
+        let params =
+            VerifiableParams { packet_header: [0u8; PACKET_HEADER_SIZE], payload_hash: [1u8; 32] };
+        let header = &params.packet_header;
+        let receive_library_program = Pubkey::new_unique();
+        let endpoint_program = Pubkey::new_unique();
+        let (config, bump) = Pubkey::find_program_address(&[DVN_CONFIG_SEED], &dvn::ID);
+        let path = [packet_v1_codec::receiver(header), packet_v1_codec::sender(header)];
+        let src_eid = packet_v1_codec::src_eid(header).to_be_bytes();
+        let nonce = packet_v1_codec::nonce(header).to_be_bytes();
+        let (nonce_account, _) = Pubkey::find_program_address(
+            &[NONCE_SEED, &path[0], &src_eid, &path[1]],
+            &endpoint_program,
+        );
+        let (payload_hash, _) = Pubkey::find_program_address(
+            &[PAYLOAD_HASH_SEED, &path[0], &src_eid, &path[1], &nonce],
+            &endpoint_program,
+        );
+
+        let system = Pubkey::default();
+        let keys = [
+            (config, dvn::ID, false),
+            (receive_library_program, system, true),
+            (message_lib, receive_library_program, false),
+            (endpoint_program, system, true),
+            (nonce_account, system, false),
+            (payload_hash, system, false),
+        ];
+        let mut lamports = vec![1u64; keys.len()];
+        let mut data = vec![vec![]; keys.len()];
+        data[0] = dvn_config(bump, msglibs);
+        let accounts = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|(((key, owner, executable), lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, owner, *executable, 0)
+            })
+            .collect::<Vec<_>>();
+
+        let mut infos = &accounts[..];
+        Verifiable::try_accounts(
+            &dvn::ID,
+            &mut infos,
+            &params.try_to_vec().unwrap(),
+            &mut VerifiableBumps::default(),
+            &mut BTreeSet::new(),
+        )
+        .map(|_| ())
+    }
+
+    #[test]
+    fn empty_msglibs_allows_any_message_lib() {
+        try_verifiable_accounts(vec![], Pubkey::new_unique()).unwrap();
+    }
+
+    #[test]
+    fn msglibs_restrict_message_lib() {
+        let allowed = Pubkey::new_unique();
+        try_verifiable_accounts(vec![allowed], allowed).unwrap();
+
+        let result = try_verifiable_accounts(vec![allowed], Pubkey::new_unique());
+        assert!(result.unwrap_err().to_string().contains("MsgLibNotAllowed"));
+    }
+}
@@ -20,4 +20,5 @@ pub enum LayerZeroError {
     ReadOnlyAccount,
     InvalidMessageLib,
     WritableAccountNotAllowed,
+    IncompatibleMessageLib,
 }
@@ -106,6 +106,7 @@ pub struct LzComposeAlertEvent {
 pub struct LibraryRegisteredEvent {
     pub new_lib: Pubkey, // The PDA of the message lib program
     pub new_lib_program: Pubkey,
+    pub version: Version,
 }
 
 #[event]
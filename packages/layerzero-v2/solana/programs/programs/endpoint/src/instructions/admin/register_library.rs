@@ -1,4 +1,5 @@
 use crate::*;
+use utils::appended_field::Appended;
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -22,6 +23,9 @@ pub struct RegisterLibrary<'info> {
         bump
     )]
     pub message_lib_info: Account<'info, MessageLibInfo>,
+    /// CHECK: the message lib program to register, asserted by the params
+    #[account(executable, address = params.lib_program @LayerZeroError::InvalidMessageLib)]
+    pub lib_program: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -37,9 +41,28 @@ impl RegisterLibrary<'_> {
             Pubkey::find_program_address(&[MESSAGE_LIB_SEED], &params.lib_program);
         ctx.accounts.message_lib_info.message_lib_bump = bump;
 
+        // the message lib must be built for this endpoint
+        let seeds: &[&[&[u8]]] =
+            &[&[MESSAGE_LIB_SEED, message_lib.as_ref(), &[ctx.bumps.message_lib_info]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.lib_program.to_account_info(),
+            messagelib_interface::cpi::accounts::Interface {
+                endpoint: ctx.accounts.message_lib_info.to_account_info(),
+            },
+            seeds,
+        );
+        let version = messagelib_interface::cpi::version(cpi_ctx)?.get();
+        require!(
+            version.endpoint_version == ENDPOINT_VERSION,
+            LayerZeroError::IncompatibleMessageLib
+        );
+        ctx.accounts.message_lib_info.version =
+            Appended::new(MessageLibVersion { version: Some(version.clone()) });
+
         emit_cpi!(LibraryRegisteredEvent {
             new_lib: message_lib,
-            new_lib_program: params.lib_program
+            new_lib_program: params.lib_program,
+            version,
         });
         Ok(())
     }
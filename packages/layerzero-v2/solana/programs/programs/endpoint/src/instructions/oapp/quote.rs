@@ -50,6 +50,35 @@ impl Quote<'_> {
         ctx: &Context<'_, '_, 'c, 'info, Quote<'info>>,
         params: &QuoteParams,
     ) -> Result<MessagingFee> {
+        Self::quote_library(ctx, params, |cpi_ctx, quote_params| {
+            Ok(messagelib_interface::cpi::quote(cpi_ctx, quote_params)?.get())
+        })
+    }
+
+    /// Returns the fee detail of the send library, if it implements quote_detailed()
+    pub fn quote_detailed<'c: 'info, 'info>(
+        ctx: &Context<'_, '_, 'c, 'info, Quote<'info>>,
+        params: &QuoteParams,
+    ) -> Result<MessagingFeeDetail> {
+        Self::quote_library(ctx, params, |cpi_ctx, quote_params| {
+            Ok(messagelib_interface::cpi::quote_detailed(cpi_ctx, quote_params)?.get())
+        })
+    }
+
+    fn quote_library<'c: 'info, 'info, T>(
+        ctx: &Context<'_, '_, 'c, 'info, Quote<'info>>,
+        params: &QuoteParams,
+        quote: impl FnOnce(
+            anchor_lang::context::CpiContext<
+                '_,
+                '_,
+                '_,
+                'info,
+                messagelib_interface::cpi::accounts::Interface<'info>,
+            >,
+            messagelib_interface::QuoteParams,
+        ) -> Result<T>,
+    ) -> Result<T> {
         // assert all accounts are non-writable
         for account in ctx.remaining_accounts {
             require!(!account.is_writable, LayerZeroError::WritableAccountNotAllowed)
@@ -101,7 +130,7 @@ impl Quote<'_> {
             seeds,
         )
         .with_remaining_accounts(ctx.remaining_accounts.to_vec());
-        Ok(messagelib_interface::cpi::quote(cpi_ctx, quote_params)?.get())
+        quote(cpi_ctx, quote_params)
     }
 }
 
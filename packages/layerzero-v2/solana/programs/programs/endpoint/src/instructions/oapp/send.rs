@@ -208,6 +208,7 @@ mod tests {
             message_lib_bump: send_library_bump1,
             message_lib_type: MessageLibType::Send,
             bump: 0,
+            version: Default::default(),
         };
         // default send library config
         let default_send_library_config = SendLibraryConfig { message_lib: send_library2, bump: 0 };
@@ -215,6 +216,7 @@ mod tests {
             message_lib_bump: send_library_bump2,
             message_lib_type: MessageLibType::Send,
             bump: 0,
+            version: Default::default(),
         };
 
         // test assert_send_library with oapp setting, which is send_library1
@@ -8,7 +8,8 @@ use errors::*;
 use events::*;
 use instructions::*;
 pub use messagelib_interface::{
-    self, InitConfigParams, MessageLibType, MessagingFee, MessagingReceipt, Packet, SetConfigParams,
+    self, InitConfigParams, MessageLibType, MessagingFee, MessagingFeeDetail, MessagingReceipt,
+    Packet, SetConfigParams, Version,
 };
 use solana_helper::program_id_from_env;
 use state::*;
@@ -28,6 +29,9 @@ pub const PAYLOAD_HASH_SEED: &[u8] = b"PayloadHash";
 pub const COMPOSED_MESSAGE_HASH_SEED: &[u8] = b"ComposedMessageHash";
 pub const OAPP_SEED: &[u8] = b"OApp";
 
+// the endpoint_version of the message libs that can be registered
+pub const ENDPOINT_VERSION: u8 = 2;
+
 pub const DEFAULT_MESSAGE_LIB: Pubkey = Pubkey::new_from_array([0u8; 32]);
 
 #[program]
@@ -161,6 +165,13 @@ pub mod endpoint {
         Quote::apply(&ctx, &params)
     }
 
+    pub fn quote_detailed<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, Quote<'info>>,
+        params: QuoteParams,
+    ) -> Result<MessagingFeeDetail> {
+        Quote::quote_detailed(&ctx, &params)
+    }
+
     pub fn send<'c: 'info, 'info>(
         mut ctx: Context<'_, '_, 'c, 'info, Send<'info>>,
         params: SendParams,
@@ -1,5 +1,5 @@
 use crate::*;
-use messagelib_interface::MessageLibType;
+use utils::appended_field::{Appended, AppendedFields};
 
 #[account]
 #[derive(InitSpace)]
@@ -9,6 +9,19 @@ pub struct MessageLibInfo {
     pub bump: u8,
     // bump for the pda of the message lib program with the seeds `[MESSAGE_LIB_SEED]`
     pub message_lib_bump: u8,
+    pub version: Appended<MessageLibVersion>,
+}
+
+/// The version of the message lib when it was registered. It is appended to the message lib info
+/// accounts, so the libs registered before it are not migrated and read as None.
+#[derive(InitSpace, Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct MessageLibVersion {
+    pub version: Option<Version>,
+}
+
+impl AppendedFields for MessageLibVersion {
+    const DISCRIMINATOR: [u8; 8] = *b"MsgLibEx";
+    const VERSION: u8 = 1;
 }
 
 /// the reason for not using Option::None to indicate default is to respect the spec on evm
@@ -36,3 +49,30 @@ pub struct ReceiveLibraryTimeout {
 utils::generate_account_size_test!(MessageLibInfo, message_lib_info_test);
 utils::generate_account_size_test!(SendLibraryConfig, send_library_config_test);
 utils::generate_account_size_test!(ReceiveLibraryConfig, receive_library_config_test);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_message_lib_info_without_version() {
+        let data = [MessageLibType::Send as u8, 254, 255];
+        let info = MessageLibInfo::deserialize(&mut &data[..]).unwrap();
+        assert_eq!(info.bump, 254);
+        assert_eq!(info.message_lib_bump, 255);
+        assert!(!info.version.is_migrated());
+        assert!(info.version.version.is_none());
+
+        let version = Version { major: 3, minor: 0, endpoint_version: ENDPOINT_VERSION };
+        let data = MessageLibInfo {
+            message_lib_type: MessageLibType::SendAndReceive,
+            bump: 1,
+            message_lib_bump: 2,
+            version: Appended::new(MessageLibVersion { version: Some(version.clone()) }),
+        }
+        .try_to_vec()
+        .unwrap();
+        let info = MessageLibInfo::deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(info.version.version, Some(version));
+    }
+}
@@ -20,4 +20,12 @@ pub enum ExecutorError {
     EidNotSupported,
     ExecutorIsAdmin,
     InvalidOwner,
+    InvalidExecutionPlan,
+    FeeLimitExceeded,
+    InvalidTokenDropMint,
+    InvalidTokenDropReceiver,
+    NotMigrated,
+    AlreadyMigrated,
+    ExecutionContextNotClosed,
+    ExecutionOverBudget,
 }
@@ -10,3 +10,15 @@ pub struct NativeDropAppliedEvent {
     pub native_drop_requests: Vec<NativeDropRequest>,
     pub successes: Vec<bool>,
 }
+
+#[event]
+pub struct TokenDropAppliedEvent {
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub dst_eid: u32,
+    pub oapp: Pubkey,
+    pub mint: Pubkey,
+    pub receiver: Pubkey,
+    pub amount: u64,
+}
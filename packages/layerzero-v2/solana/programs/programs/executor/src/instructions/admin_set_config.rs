@@ -1,4 +1,5 @@
 use crate::*;
+use worker_interface::worker_utils::FeeToken;
 
 #[derive(Accounts)]
 pub struct AdminSetConfig<'info> {
@@ -24,6 +25,9 @@ pub enum AdminSetConfigParams {
     PriceFeed(Pubkey),
     DefaultMultiplierBps(u16),
     DstConfigs(Vec<DstConfig>),
+    FeeToken(Option<FeeToken>),
+    DstTokenDropConfigs(Vec<DstTokenDropConfig>),
+    RemoveDstTokenDropConfigs(Vec<u32>),
 }
 
 impl AdminSetConfigParams {
@@ -40,6 +44,16 @@ impl AdminSetConfigParams {
             AdminSetConfigParams::DstConfigs(dst_configs) => {
                 config.set_dst_configs(dst_configs.clone())
             },
+            AdminSetConfigParams::FeeToken(fee_token) => {
+                config.appended_mut()?.fee_token = fee_token.clone();
+                Ok(())
+            },
+            AdminSetConfigParams::DstTokenDropConfigs(configs) => {
+                config.set_dst_token_drop_configs(configs.clone())
+            },
+            AdminSetConfigParams::RemoveDstTokenDropConfigs(dst_eids) => {
+                config.remove_dst_token_drop_configs(dst_eids.clone())
+            },
         }
     }
 }
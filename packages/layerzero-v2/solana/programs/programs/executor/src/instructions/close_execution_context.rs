@@ -0,0 +1,38 @@
+use crate::*;
+use anchor_lang::system_program;
+use oapp::common::EXECUTION_CONTEXT_SEED;
+
+/// Closes the execution context of the executor after an execution batch, asserting that the
+/// executor did not spend more than the fee limit in the batch.
+#[derive(Accounts)]
+pub struct CloseExecutionContext<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    /// CHECK: the execution context of the executor
+    #[account(
+        mut,
+        seeds = [EXECUTION_CONTEXT_SEED, executor.key().as_ref()],
+        bump,
+        owner = ID
+    )]
+    pub context: UncheckedAccount<'info>,
+}
+
+impl CloseExecutionContext<'_> {
+    pub fn apply(ctx: &mut Context<CloseExecutionContext>) -> Result<()> {
+        let context =
+            execution_context(&ctx.accounts.context)?.ok_or(ExecutorError::InvalidOwner)?;
+        require!(
+            context.initial_payer_balance
+                <= ctx.accounts.executor.lamports().saturating_add(context.fee_limit),
+            ExecutorError::FeeLimitExceeded
+        );
+
+        let context_acc = ctx.accounts.context.to_account_info();
+        ctx.accounts.executor.add_lamports(context_acc.lamports())?;
+        context_acc.sub_lamports(context_acc.lamports())?;
+        context_acc.assign(&system_program::ID);
+        context_acc.realloc(0, false)?;
+        Ok(())
+    }
+}
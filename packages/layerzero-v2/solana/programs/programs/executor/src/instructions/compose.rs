@@ -1,6 +1,7 @@
 use crate::*;
 use anchor_lang::solana_program::{instruction::Instruction, program::invoke, system_program};
 use oapp::{
+    alert_reason::{AlertReason, FailureReason},
     endpoint::{
         self, cpi::accounts::LzComposeAlert, instructions::LzComposeAlertParams, program::Endpoint,
         ConstructCPIContext,
@@ -50,7 +51,7 @@ impl Compose<'_> {
                 value: params.value,
                 message: params.lz_compose.message.clone(),
                 extra_data: params.lz_compose.extra_data.clone(),
-                reason: e.to_string().into_bytes(),
+                reason: AlertReason::Failure(FailureReason::new(program_id, &e)).encode(),
             };
 
             let cpi_ctx = LzComposeAlert::construct_context(
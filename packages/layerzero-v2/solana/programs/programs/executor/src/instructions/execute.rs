@@ -1,12 +1,17 @@
 use crate::*;
-use anchor_lang::solana_program::{instruction::Instruction, program::invoke, system_program};
+use anchor_lang::solana_program::{
+    compute_units::sol_remaining_compute_units, instruction::Instruction, program::invoke,
+    system_program,
+};
 use oapp::{
+    alert_reason::{AlertReason, FailureReason},
     endpoint::{
         self, cpi::accounts::LzReceiveAlert, instructions::LzReceiveAlertParams, program::Endpoint,
         ConstructCPIContext,
     },
     LzReceiveParams,
 };
+use worker_interface::LzOption;
 
 pub const LZ_RECEIVE_DISCRIMINATOR: [u8; 8] = [8, 179, 120, 109, 33, 118, 189, 80];
 
@@ -28,70 +33,135 @@ pub struct Execute<'info> {
 
 impl Execute<'_> {
     pub fn apply(ctx: &mut Context<Execute>, params: &ExecuteParams) -> Result<()> {
-        let balance_before = ctx.accounts.executor.lamports();
-        let program_id = ctx.remaining_accounts[0].key();
-        let accounts = ctx
-            .remaining_accounts
-            .iter()
-            .skip(1)
-            .map(|acc| acc.to_account_metas(None)[0].clone())
-            .collect::<Vec<_>>();
-        let data = get_lz_receive_ix_data(&params.lz_receive)?;
-        let result = invoke(&Instruction { program_id, accounts, data }, ctx.remaining_accounts);
-
-        if let Err(e) = result {
-            // call lz_receive_alert
-            let params = LzReceiveAlertParams {
-                receiver: params.receiver,
-                src_eid: params.lz_receive.src_eid,
-                sender: params.lz_receive.sender,
-                nonce: params.lz_receive.nonce,
-                guid: params.lz_receive.guid,
-                compute_units: params.compute_units,
-                value: params.value,
-                message: params.lz_receive.message.clone(),
-                extra_data: params.lz_receive.extra_data.clone(),
-                reason: e.to_string().into_bytes(),
-            };
-
-            let cpi_ctx = LzReceiveAlert::construct_context(
-                ctx.accounts.endpoint_program.key(),
-                &[
-                    ctx.accounts.config.to_account_info(), // use the executor config as the signer
-                    ctx.accounts.endpoint_event_authority.to_account_info(),
-                    ctx.accounts.endpoint_program.to_account_info(),
-                ],
-            )?;
-            endpoint::cpi::lz_receive_alert(
-                cpi_ctx.with_signer(&[&[EXECUTOR_CONFIG_SEED, &[ctx.accounts.config.bump]]]),
-                params,
-            )?;
-        } else {
-            // assert that the executor account does not lose more than the expected value
-            let balance_after = ctx.accounts.executor.lamports();
-            require!(
-                balance_before <= balance_after + params.value,
-                ExecutorError::InsufficientBalance
-            );
-        }
+        let alert = LzReceiveAlertAccounts {
+            config: &ctx.accounts.config,
+            endpoint_program: ctx.accounts.endpoint_program.to_account_info(),
+            endpoint_event_authority: ctx.accounts.endpoint_event_authority.to_account_info(),
+        };
+        execute_lz_receive(&ctx.accounts.executor, &alert, params, ctx.remaining_accounts)
+    }
+}
+
+/// Calls lz_receive of the program of the first account with the rest of the accounts, and
+/// alerts if it fails. It fails the execution if the lz_receive succeeds over the compute units of
+/// the quoted lz_receive gas.
+pub(crate) fn execute_lz_receive(
+    executor: &AccountInfo,
+    alert: &LzReceiveAlertAccounts,
+    params: &ExecuteParams,
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    let compute_units = lz_receive_compute_units(&params.options)?;
+    let balance_before = executor.lamports();
+    let program_id = accounts[0].key();
+    let metas = accounts
+        .iter()
+        .skip(1)
+        .map(|acc| acc.to_account_metas(None)[0].clone())
+        .collect();
+    let data = get_lz_receive_ix_data(&params.lz_receive)?;
+    let compute_units_before = sol_remaining_compute_units();
+    let result = invoke(&Instruction { program_id, accounts: metas, data }, accounts);
+
+    if let Err(e) = result {
+        // call lz_receive_alert
+        alert.lz_receive_alert(
+            &params.receiver,
+            &params.lz_receive,
+            params.value,
+            compute_units,
+            AlertReason::Failure(FailureReason::new(program_id, &e)).encode(),
+        )?;
+    } else {
+        check_compute_budget(compute_units, compute_units_before)?;
+
+        // assert that the executor account does not lose more than the expected value
+        let balance_after = executor.lamports();
         require!(
-            ctx.accounts.executor.owner.key() == system_program::ID,
-            ExecutorError::InvalidOwner
+            balance_before <= balance_after + params.value,
+            ExecutorError::InsufficientBalance
         );
-        require!(ctx.accounts.executor.data_is_empty(), ExecutorError::InvalidSize);
-        Ok(())
+    }
+    require!(executor.owner.key() == system_program::ID, ExecutorError::InvalidOwner);
+    require!(executor.data_is_empty(), ExecutorError::InvalidSize);
+    Ok(())
+}
+
+/// The accounts to call lz_receive_alert of the endpoint with, signed by the executor config
+pub(crate) struct LzReceiveAlertAccounts<'a, 'info> {
+    pub config: &'a Account<'info, ExecutorConfig>,
+    pub endpoint_program: AccountInfo<'info>,
+    pub endpoint_event_authority: AccountInfo<'info>,
+}
+
+impl LzReceiveAlertAccounts<'_, '_> {
+    pub fn lz_receive_alert(
+        &self,
+        receiver: &Pubkey,
+        lz_receive: &LzReceiveParams,
+        value: u64,
+        compute_units: u64,
+        reason: Vec<u8>,
+    ) -> Result<()> {
+        let params = LzReceiveAlertParams {
+            receiver: *receiver,
+            src_eid: lz_receive.src_eid,
+            sender: lz_receive.sender,
+            nonce: lz_receive.nonce,
+            guid: lz_receive.guid,
+            compute_units,
+            value,
+            message: lz_receive.message.clone(),
+            extra_data: lz_receive.extra_data.clone(),
+            reason,
+        };
+
+        let cpi_ctx = LzReceiveAlert::construct_context(
+            self.endpoint_program.key(),
+            &[
+                self.config.to_account_info(), // use the executor config as the signer
+                self.endpoint_event_authority.clone(),
+                self.endpoint_program.clone(),
+            ],
+        )?;
+        endpoint::cpi::lz_receive_alert(
+            cpi_ctx.with_signer(&[&[EXECUTOR_CONFIG_SEED, &[self.config.bump]]]),
+            params,
+        )
     }
 }
 
+/// The compute units of the lz_receive, which are the lz_receive gas quoted in the executor options
+/// of the packet, as the gas of the solana destinations is quoted in compute units
+pub(crate) fn lz_receive_compute_units(options: &[LzOption]) -> Result<u64> {
+    let mut gas: u128 = 0;
+    for option in options.iter().filter(|option| option.option_type == OPTION_TYPE_LZRECEIVE) {
+        let (option_gas, _) = decode_lz_receive_params(&option.params)?;
+        gas = gas.saturating_add(option_gas);
+    }
+    require!(gas > 0, ExecutorError::ZeroLzReceiveGasProvided);
+    Ok(u64::try_from(gas).unwrap_or(u64::MAX))
+}
+
+/// Fails the execution if the lz_receive consumed more than the compute units since
+/// `compute_units_before`, logging the compute units and the consumed ones. An lz_receive over its
+/// quoted gas is not delivered by the executor, but can be by anyone else.
+pub(crate) fn check_compute_budget(compute_units: u64, compute_units_before: u64) -> Result<()> {
+    let consumed_compute_units = compute_units_before.saturating_sub(sol_remaining_compute_units());
+    require_gte!(compute_units, consumed_compute_units, ExecutorError::ExecutionOverBudget);
+    Ok(())
+}
+
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct ExecuteParams {
     pub receiver: Pubkey,
     pub lz_receive: LzReceiveParams,
     pub value: u64,
-    pub compute_units: u64,
+    // the executor options of the packet, of which the lz_receive gas caps the compute units
+    pub options: Vec<LzOption>,
 }
 
-fn get_lz_receive_ix_data(params: &LzReceiveParams) -> Result<Vec<u8>> {
+pub(crate) fn get_lz_receive_ix_data(params: &LzReceiveParams) -> Result<Vec<u8>> {
     let mut data = Vec::with_capacity(92 + params.message.len() + params.extra_data.len()); // 8 + 4 + 32 + 8 + 32 + 4 + 4
     data.extend(LZ_RECEIVE_DISCRIMINATOR);
     params.serialize(&mut data)?;
@@ -115,3 +185,29 @@ fn lz_receive_ix_data() {
     expected.extend_from_slice(&params.try_to_vec().unwrap());
     assert_eq!(data, expected);
 }
+
+#[test]
+fn compute_budget_of_quoted_gas() {
+    let lz_receive = |gas: u128| LzOption {
+        option_type: OPTION_TYPE_LZRECEIVE,
+        params: [gas.to_be_bytes(), 0u128.to_be_bytes()].concat(),
+    };
+    let native_drop = LzOption { option_type: OPTION_TYPE_NATIVE_DROP, params: vec![0; 48] };
+
+    // the lz_receive gas of the options is summed up, the other options are skipped
+    let options = [lz_receive(100_000), native_drop.clone(), lz_receive(50_000)];
+    assert_eq!(lz_receive_compute_units(&options).unwrap(), 150_000);
+    assert_eq!(lz_receive_compute_units(&[lz_receive(u128::MAX)]).unwrap(), u64::MAX);
+    let result = lz_receive_compute_units(&[native_drop]);
+    assert_eq!(result.unwrap_err(), ExecutorError::ZeroLzReceiveGasProvided.into());
+    let invalid = LzOption { option_type: OPTION_TYPE_LZRECEIVE, params: vec![0; 15] };
+    assert_eq!(
+        lz_receive_compute_units(&[invalid]).unwrap_err(),
+        ExecutorError::InvalidSize.into()
+    );
+
+    // the remaining compute units are 0 off-chain, so all the units before are consumed
+    let result = check_compute_budget(100, 150);
+    assert_eq!(result.unwrap_err(), ExecutorError::ExecutionOverBudget.into());
+    assert!(check_compute_budget(100, 100).is_ok());
+}
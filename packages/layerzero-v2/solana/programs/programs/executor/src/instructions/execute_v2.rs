@@ -0,0 +1,521 @@
+use crate::*;
+use anchor_lang::solana_program::{
+    compute_units::sol_remaining_compute_units, instruction::Instruction, program::invoke,
+    system_program,
+};
+use anchor_spl::{token, token_2022};
+use oapp::{
+    alert_reason::{AlertReason, FailureReason},
+    common::{
+        deserialize_alt, AccountMetaRef, AddressLocator, ExecutionContextV1,
+        EXECUTION_CONTEXT_SEED, EXECUTION_CONTEXT_VERSION_1,
+    },
+    endpoint::program::Endpoint,
+    lz_receive_types_v2::{self, LzReceiveTypesV2Result},
+    LzReceiveParams,
+};
+use worker_interface::LzOption;
+
+/// Executes the plan returned by lz_receive_types_v2 of the oapp, its instructions in order. The
+/// remaining accounts are the additional signers of the plan, followed by its address lookup
+/// tables in order, and the other accounts of its instructions. The lamports spent by the executor
+/// in the execution batch are capped by the fee limit of the execution context, or the lamports
+/// spent in this execution by the value if it is not initialized, and the compute units of the
+/// lz_receive by the quoted lz_receive gas of the options. The executor signs the
+/// instructions of the plan that locate it as the payer, but for those to the token programs,
+/// which could move the tokens of the executor. No instruction of the plan can be sent to this
+/// program, as its instructions are signed by the executor.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteV2<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [EXECUTOR_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.executors.contains(executor.key) @ExecutorError::NotExecutor
+    )]
+    pub config: Account<'info, ExecutorConfig>,
+    /// CHECK: the execution context of the executor, deserialized only if initialized
+    #[account(mut, seeds = [EXECUTION_CONTEXT_SEED, executor.key().as_ref()], bump)]
+    pub context: UncheckedAccount<'info>,
+    pub endpoint_program: Program<'info, Endpoint>,
+    /// The authority for the endpoint program to emit events
+    pub endpoint_event_authority: UncheckedAccount<'info>,
+}
+
+impl ExecuteV2<'_> {
+    pub fn apply(ctx: &mut Context<ExecuteV2>, params: &ExecuteV2Params) -> Result<()> {
+        let plan = &params.plan;
+        require!(
+            plan.context_version == EXECUTION_CONTEXT_VERSION_1,
+            ExecutorError::InvalidExecutionPlan
+        );
+        require!(
+            plan.instructions
+                .iter()
+                .filter(|ix| matches!(ix, lz_receive_types_v2::Instruction::LzReceive { .. }))
+                .count()
+                == 1,
+            ExecutorError::InvalidExecutionPlan
+        );
+
+        let resolver = AddressResolver::new(
+            ctx.accounts.executor.key(),
+            ctx.accounts.context.key(),
+            params.signer_count,
+            &plan.alts,
+            ctx.remaining_accounts,
+        )?;
+        let receiver_program = receiver_program(&params.receiver, ctx.remaining_accounts)?;
+
+        // the spend is counted from the start of the batch if the execution context is initialized
+        let (balance_before, fee_limit) = match execution_context(&ctx.accounts.context)? {
+            Some(context) => (context.initial_payer_balance, context.fee_limit),
+            None => (ctx.accounts.executor.lamports(), params.value),
+        };
+        let compute_units = lz_receive_compute_units(&params.options)?;
+        for ix in &plan.instructions {
+            match ix {
+                lz_receive_types_v2::Instruction::Standard { program_id, accounts, data } => {
+                    let ix = Instruction {
+                        program_id: *program_id,
+                        accounts: resolver.resolve_all(accounts)?,
+                        data: data.clone(),
+                    };
+                    check_standard_instruction(&ix, &resolver.payer)?;
+                    invoke(&ix, ctx.remaining_accounts)?;
+                },
+                lz_receive_types_v2::Instruction::LzReceive { accounts } => {
+                    let ix = Instruction {
+                        program_id: receiver_program,
+                        accounts: resolver.resolve_all(accounts)?,
+                        data: get_lz_receive_ix_data(&params.lz_receive)?,
+                    };
+                    let compute_units_before = sol_remaining_compute_units();
+                    let alert = LzReceiveAlertAccounts {
+                        config: &ctx.accounts.config,
+                        endpoint_program: ctx.accounts.endpoint_program.to_account_info(),
+                        endpoint_event_authority: ctx
+                            .accounts
+                            .endpoint_event_authority
+                            .to_account_info(),
+                    };
+                    match invoke(&ix, ctx.remaining_accounts) {
+                        Ok(()) => check_compute_budget(compute_units, compute_units_before)?,
+                        Err(e) => alert.lz_receive_alert(
+                            &params.receiver,
+                            &params.lz_receive,
+                            params.value,
+                            compute_units,
+                            AlertReason::Failure(FailureReason::new(receiver_program, &e)).encode(),
+                        )?,
+                    }
+                },
+            }
+        }
+
+        // assert that the executor account does not spend more than the fee limit
+        let balance_after = ctx.accounts.executor.lamports();
+        require!(
+            balance_before <= balance_after.saturating_add(fee_limit),
+            ExecutorError::FeeLimitExceeded
+        );
+        require!(
+            ctx.accounts.executor.owner.key() == system_program::ID,
+            ExecutorError::InvalidOwner
+        );
+        require!(ctx.accounts.executor.data_is_empty(), ExecutorError::InvalidSize);
+        Ok(())
+    }
+}
+
+// returns None if the execution context is not initialized
+pub(crate) fn execution_context(context_acc: &AccountInfo) -> Result<Option<ExecutionContextV1>> {
+    if context_acc.owner.key() == ID {
+        let mut data: &[u8] = &context_acc.try_borrow_data()?;
+        Ok(Some(ExecutionContextV1::try_deserialize(&mut data)?))
+    } else {
+        Ok(None)
+    }
+}
+
+// the plan can not invoke this program, or it could call the instructions signed by the executor,
+// and the token programs are not invoked with the executor as a signer, or the plan could move the
+// tokens of the executor
+fn check_standard_instruction(ix: &Instruction, payer: &Pubkey) -> Result<()> {
+    require!(ix.program_id != ID, ExecutorError::InvalidExecutionPlan);
+    let is_token_program = ix.program_id == token::ID || ix.program_id == token_2022::ID;
+    require!(
+        !is_token_program
+            || !ix.accounts.iter().any(|meta| meta.is_signer && meta.pubkey == *payer),
+        ExecutorError::InvalidExecutionPlan
+    );
+    Ok(())
+}
+
+// the lz_receive instruction is sent to the receiver if it is a program, or to its owner
+fn receiver_program(receiver: &Pubkey, accounts: &[AccountInfo]) -> Result<Pubkey> {
+    let receiver_acc = accounts
+        .iter()
+        .find(|acc| acc.key() == *receiver)
+        .ok_or(ExecutorError::InvalidExecutionPlan)?;
+    let program = if receiver_acc.executable { *receiver } else { *receiver_acc.owner };
+    require!(program != ID, ExecutorError::InvalidExecutionPlan);
+    Ok(program)
+}
+
+/// Resolves the address locators of the execution plan to the accounts of the executor
+pub struct AddressResolver {
+    pub payer: Pubkey,
+    pub context: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub alts: Vec<Vec<Pubkey>>,
+}
+
+impl AddressResolver {
+    fn new(
+        payer: Pubkey,
+        context: Pubkey,
+        signer_count: u8,
+        alts: &[Pubkey],
+        accounts: &[AccountInfo],
+    ) -> Result<Self> {
+        let signer_count = signer_count as usize;
+        require!(accounts.len() >= signer_count + alts.len(), ExecutorError::InvalidExecutionPlan);
+        let (signer_accounts, rest) = accounts.split_at(signer_count);
+        let mut signers = Vec::with_capacity(signer_count);
+        for signer in signer_accounts {
+            require!(signer.is_signer, ExecutorError::InvalidExecutionPlan);
+            signers.push(signer.key());
+        }
+        let mut alt_addresses = Vec::with_capacity(alts.len());
+        for (alt, alt_acc) in alts.iter().zip(rest) {
+            require!(alt_acc.key() == *alt, ExecutorError::InvalidExecutionPlan);
+            alt_addresses.push(deserialize_alt(alt_acc)?);
+        }
+        Ok(Self { payer, context, signers, alts: alt_addresses })
+    }
+
+    pub fn resolve(&self, meta: &AccountMetaRef) -> Result<AccountMeta> {
+        let (pubkey, is_signer) = match &meta.pubkey {
+            AddressLocator::Address(pubkey) => (*pubkey, false),
+            AddressLocator::AltIndex(alt_index, address_index) => {
+                let address = self
+                    .alts
+                    .get(*alt_index as usize)
+                    .and_then(|alt| alt.get(*address_index as usize))
+                    .ok_or(ExecutorError::InvalidExecutionPlan)?;
+                (*address, false)
+            },
+            AddressLocator::Payer => (self.payer, true),
+            AddressLocator::Signer(index) => {
+                let signer =
+                    self.signers.get(*index as usize).ok_or(ExecutorError::InvalidExecutionPlan)?;
+                (*signer, true)
+            },
+            AddressLocator::Context => (self.context, false),
+        };
+        Ok(if meta.is_writable {
+            AccountMeta::new(pubkey, is_signer)
+        } else {
+            AccountMeta::new_readonly(pubkey, is_signer)
+        })
+    }
+
+    pub fn resolve_all(&self, metas: &[AccountMetaRef]) -> Result<Vec<AccountMeta>> {
+        metas.iter().map(|meta| self.resolve(meta)).collect()
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ExecuteV2Params {
+    pub receiver: Pubkey,
+    pub lz_receive: LzReceiveParams,
+    pub value: u64,
+    // the executor options of the packet, of which the lz_receive gas caps the compute units
+    pub options: Vec<LzOption>,
+    pub signer_count: u8,
+    pub plan: LzReceiveTypesV2Result,
+}
+
+/// The accounts of the executor to execute the plans of a receiver in the tests, by index: the
+/// executor, its config, its execution context, the endpoint program, its event authority, the
+/// system program, the event authority, this program, the receiver program and another account.
+#[cfg(test)]
+pub(crate) struct ExecutionFixture {
+    pub keys: Vec<Pubkey>,
+    pub owners: Vec<Pubkey>,
+    pub lamports: Vec<u64>,
+    pub data: Vec<Vec<u8>>,
+}
+
+#[cfg(test)]
+impl ExecutionFixture {
+    pub const EXECUTOR: usize = 0;
+    pub const CONTEXT: usize = 2;
+    pub const ACCOUNT: usize = 9;
+
+    pub fn new(executor_lamports: u64) -> Self {
+        use utils::appended_field::Appended;
+
+        let executor = Pubkey::new_unique();
+        let (config_key, config_bump) = Pubkey::find_program_address(&[EXECUTOR_CONFIG_SEED], &ID);
+        let (context_key, _) =
+            Pubkey::find_program_address(&[EXECUTION_CONTEXT_SEED, executor.as_ref()], &ID);
+        let (event_authority, _) = Pubkey::find_program_address(&[b"__event_authority"], &ID);
+        let config = ExecutorConfig {
+            bump: config_bump,
+            owner: Pubkey::new_unique(),
+            acl: worker_interface::worker_utils::Acl { allow_list: vec![], deny_list: vec![] },
+            admins: vec![],
+            executors: vec![executor],
+            msglibs: vec![],
+            paused: false,
+            default_multiplier_bps: 0,
+            price_feed: Pubkey::new_unique(),
+            dst_configs: vec![],
+            appended: Appended::default(),
+        };
+        let mut config_data = vec![];
+        config.try_serialize(&mut config_data).unwrap();
+
+        let keys = vec![
+            executor,
+            config_key,
+            context_key,
+            oapp::endpoint::ID,
+            Pubkey::new_unique(),
+            system_program::ID,
+            event_authority,
+            ID,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let mut owners = vec![system_program::ID; keys.len()];
+        owners[1] = ID;
+        let mut lamports = vec![0; keys.len()];
+        lamports[Self::EXECUTOR] = executor_lamports;
+        let mut data = vec![vec![]; keys.len()];
+        data[1] = config_data;
+        // the context is created at its size by the stubbed system program
+        data[Self::CONTEXT] = vec![0; 8 + ExecutionContextV1::INIT_SPACE];
+        Self { keys, owners, lamports, data }
+    }
+
+    pub fn infos(&mut self) -> Vec<AccountInfo<'_>> {
+        self.keys
+            .iter()
+            .zip(self.owners.iter())
+            .zip(self.lamports.iter_mut())
+            .zip(self.data.iter_mut())
+            .enumerate()
+            .map(|(index, (((key, owner), lamports), data))| {
+                let is_signer = index == Self::EXECUTOR;
+                let is_writable = [Self::EXECUTOR, Self::CONTEXT, Self::ACCOUNT].contains(&index);
+                let executable = [3, 5, 7, 8].contains(&index);
+                AccountInfo::new(key, is_signer, is_writable, lamports, data, owner, executable, 0)
+            })
+            .collect()
+    }
+
+    /// Executes the plan by execute_v2() with the accounts
+    pub fn execute(
+        &mut self,
+        instructions: Vec<lz_receive_types_v2::Instruction>,
+        value: u64,
+    ) -> Result<()> {
+        use std::collections::BTreeSet;
+
+        anchor_lang::solana_program::program_stubs::set_syscall_stubs(Box::new(ExecutionStubs));
+        let infos = self.infos();
+        let select = |indexes: &[usize]| indexes.iter().map(|i| infos[*i].clone()).collect();
+        let accounts: Vec<AccountInfo> = select(&[0, 1, 2, 3, 4, 6, 7]);
+        let remaining_accounts: Vec<AccountInfo> = select(&[8, 0, 9]);
+        let mut bumps = ExecuteV2Bumps::default();
+        let mut execute_v2 = ExecuteV2::try_accounts(
+            &ID,
+            &mut &accounts[..],
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        )?;
+        let params = ExecuteV2Params {
+            receiver: infos[8].key(),
+            lz_receive: LzReceiveParams {
+                src_eid: 1,
+                sender: [0; 32],
+                nonce: 1,
+                guid: [0; 32],
+                message: vec![],
+                extra_data: vec![],
+            },
+            value,
+            options: vec![LzOption {
+                option_type: OPTION_TYPE_LZRECEIVE,
+                params: 200_000u128.to_be_bytes().to_vec(),
+            }],
+            signer_count: 0,
+            plan: LzReceiveTypesV2Result {
+                context_version: EXECUTION_CONTEXT_VERSION_1,
+                alts: vec![],
+                instructions,
+            },
+        };
+        let mut ctx = Context::new(&ID, &mut execute_v2, &remaining_accounts, bumps);
+        ExecuteV2::apply(&mut ctx, &params)
+    }
+
+    /// The lz_receive instruction of a plan, a no-op for the stubbed receiver
+    pub fn lz_receive() -> lz_receive_types_v2::Instruction {
+        lz_receive_types_v2::Instruction::LzReceive { accounts: vec![] }
+    }
+
+    /// A plan instruction to transfer the lamports of the executor to the other account
+    pub fn spend(&self, lamports: u64) -> lz_receive_types_v2::Instruction {
+        let to = self.keys[Self::ACCOUNT];
+        let ix = anchor_lang::solana_program::system_instruction::transfer(&ID, &to, lamports);
+        lz_receive_types_v2::Instruction::Standard {
+            program_id: system_program::ID,
+            accounts: vec![
+                AccountMetaRef { pubkey: AddressLocator::Payer, is_writable: true },
+                AccountMetaRef { pubkey: AddressLocator::Address(to), is_writable: true },
+            ],
+            data: ix.data,
+        }
+    }
+}
+
+/// Runs the transfer, allocate and assign instructions of the system program, and no-ops for the
+/// other programs
+#[cfg(test)]
+struct ExecutionStubs;
+
+#[cfg(test)]
+impl anchor_lang::solana_program::program_stubs::SyscallStubs for ExecutionStubs {
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+        use anchor_lang::solana_program::{
+            program_utils::limited_deserialize, system_instruction::SystemInstruction,
+        };
+
+        if instruction.program_id != system_program::ID {
+            return Ok(());
+        }
+        let account = |index: usize| {
+            account_infos
+                .iter()
+                .find(|acc| *acc.key == instruction.accounts[index].pubkey)
+                .unwrap()
+        };
+        match limited_deserialize(&instruction.data, 1232).unwrap() {
+            SystemInstruction::Transfer { lamports } => {
+                account(0).sub_lamports(lamports)?;
+                account(1).add_lamports(lamports)?;
+            },
+            SystemInstruction::Assign { owner } => account(0).assign(&owner),
+            // the accounts are created at their size
+            SystemInstruction::Allocate { .. } => {},
+            _ => unimplemented!(),
+        }
+        Ok(())
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        anchor_lang::solana_program::entrypoint::SUCCESS
+    }
+}
+
+#[test]
+fn resolve_address_locators() {
+    let (payer, context, signer, address) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let resolver =
+        AddressResolver { payer, context, signers: vec![signer], alts: vec![vec![address]] };
+    let meta = |pubkey, is_writable| AccountMetaRef { pubkey, is_writable };
+
+    let metas = resolver
+        .resolve_all(&[
+            meta(AddressLocator::Payer, true),
+            meta(AddressLocator::Signer(0), false),
+            meta(AddressLocator::Context, true),
+            meta(AddressLocator::AltIndex(0, 0), false),
+            meta(AddressLocator::Address(address), true),
+        ])
+        .unwrap();
+    assert_eq!(
+        metas,
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new(context, false),
+            AccountMeta::new_readonly(address, false),
+            AccountMeta::new(address, false),
+        ]
+    );
+
+    assert!(resolver.resolve(&meta(AddressLocator::Signer(1), false)).is_err());
+    assert!(resolver.resolve(&meta(AddressLocator::AltIndex(0, 1), false)).is_err());
+    assert!(resolver.resolve(&meta(AddressLocator::AltIndex(1, 0), false)).is_err());
+}
+
+#[test]
+fn reject_token_instructions_signed_by_payer() {
+    let (payer, signer, account) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = |program_id, accounts| Instruction { program_id, accounts, data: vec![] };
+    let signed_by_payer = vec![AccountMeta::new(account, false), AccountMeta::new(payer, true)];
+
+    for program_id in [token::ID, token_2022::ID] {
+        let result = check_standard_instruction(&ix(program_id, signed_by_payer.clone()), &payer);
+        assert_eq!(result.unwrap_err(), ExecutorError::InvalidExecutionPlan.into());
+
+        // the payer may be passed without signing, and the other signers may sign
+        let accounts = vec![AccountMeta::new(payer, false), AccountMeta::new(signer, true)];
+        assert!(check_standard_instruction(&ix(program_id, accounts), &payer).is_ok());
+    }
+
+    // any other program may be signed by the payer, but this one
+    let program_id = Pubkey::new_unique();
+    assert!(check_standard_instruction(&ix(program_id, signed_by_payer.clone()), &payer).is_ok());
+    let result = check_standard_instruction(&ix(ID, signed_by_payer), &payer);
+    assert_eq!(result.unwrap_err(), ExecutorError::InvalidExecutionPlan.into());
+}
+
+#[test]
+fn execute_plan_within_fee_limit() {
+    let mut fixture = ExecutionFixture::new(1_000_000);
+    let (spend_60, spend_50) = (fixture.spend(60), fixture.spend(50));
+
+    // the lamports spent by the plan are capped by the value without an execution context
+    let plan = vec![spend_60.clone(), ExecutionFixture::lz_receive()];
+    assert!(fixture.execute(plan, 60).is_ok());
+    assert_eq!(fixture.lamports[ExecutionFixture::EXECUTOR], 1_000_000 - 60);
+
+    let plan = vec![ExecutionFixture::lz_receive(), spend_60, spend_50];
+    let result = fixture.execute(plan, 100);
+    assert_eq!(result.unwrap_err(), ExecutorError::FeeLimitExceeded.into());
+}
+
+#[test]
+fn reject_plan_instructions_to_executor() {
+    let mut fixture = ExecutionFixture::new(1_000_000);
+
+    // the plan can not call the instructions of the executor, which are signed by the payer
+    let close_execution_context = lz_receive_types_v2::Instruction::Standard {
+        program_id: ID,
+        accounts: vec![
+            AccountMetaRef { pubkey: AddressLocator::Payer, is_writable: true },
+            AccountMetaRef { pubkey: AddressLocator::Context, is_writable: true },
+        ],
+        data: anchor_lang::InstructionData::data(&crate::instruction::CloseExecutionContext {}),
+    };
+    let plan = vec![close_execution_context, ExecutionFixture::lz_receive()];
+    let result = fixture.execute(plan, 0);
+    assert_eq!(result.unwrap_err(), ExecutorError::InvalidExecutionPlan.into());
+}
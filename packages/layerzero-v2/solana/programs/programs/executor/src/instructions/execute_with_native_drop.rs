@@ -0,0 +1,186 @@
+use crate::*;
+use oapp::endpoint::{program::Endpoint, state::EndpointSettings, ENDPOINT_SEED};
+use utils::sorted_list_helper;
+
+/// Applies the native drops of a message and then executes its lz_receive in one instruction. The
+/// remaining accounts are the receivers of the native drops in order, followed by the accounts of
+/// lz_receive as in `Execute`. The native drops and the value are capped by the native drop cap of
+/// the dst config of the local eid, read from the endpoint settings.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteWithNativeDrop<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [EXECUTOR_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.executors.contains(executor.key) @ExecutorError::NotExecutor
+    )]
+    pub config: Account<'info, ExecutorConfig>,
+    pub endpoint_program: Program<'info, Endpoint>,
+    #[account(
+        seeds = [ENDPOINT_SEED],
+        bump = endpoint_settings.bump,
+        seeds::program = endpoint_program.key()
+    )]
+    pub endpoint_settings: Account<'info, EndpointSettings>,
+    /// The authority for the endpoint program to emit events
+    pub endpoint_event_authority: UncheckedAccount<'info>,
+    /// For native drop transfer
+    pub system_program: Program<'info, System>,
+}
+
+impl ExecuteWithNativeDrop<'_> {
+    pub fn apply<'c: 'info, 'info>(
+        ctx: &mut Context<'_, '_, 'c, 'info, ExecuteWithNativeDrop<'info>>,
+        params: &ExecuteWithNativeDropParams,
+    ) -> Result<()> {
+        let drop_count = params.native_drop_requests.len();
+        require!(
+            ctx.remaining_accounts.len() > drop_count,
+            ExecutorError::InvalidNativeDropRequestsLength
+        );
+
+        let local_eid = ctx.accounts.endpoint_settings.eid;
+        check_native_drop_cap(
+            &ctx.accounts.config,
+            local_eid,
+            &params.native_drop_requests,
+            params.execute.value,
+        )?;
+
+        let (receivers, lz_receive_accounts) = ctx.remaining_accounts.split_at(drop_count);
+        let successes =
+            apply_native_drops(&ctx.accounts.executor, &params.native_drop_requests, receivers)?;
+
+        let lz_receive = &params.execute.lz_receive;
+        emit_cpi!(NativeDropAppliedEvent {
+            src_eid: lz_receive.src_eid,
+            sender: lz_receive.sender,
+            nonce: lz_receive.nonce,
+            dst_eid: local_eid,
+            oapp: params.execute.receiver,
+            native_drop_requests: params.native_drop_requests.clone(),
+            successes,
+        });
+
+        let alert = LzReceiveAlertAccounts {
+            config: &ctx.accounts.config,
+            endpoint_program: ctx.accounts.endpoint_program.to_account_info(),
+            endpoint_event_authority: ctx.accounts.endpoint_event_authority.to_account_info(),
+        };
+        execute_lz_receive(&ctx.accounts.executor, &alert, &params.execute, lz_receive_accounts)
+    }
+}
+
+/// Checks the native drops and the value against the native drop cap of the dst config of the
+/// local eid
+fn check_native_drop_cap(
+    config: &ExecutorConfig,
+    local_eid: u32,
+    requests: &[NativeDropRequest],
+    value: u64,
+) -> Result<()> {
+    let dst_config =
+        sorted_list_helper::get_from_sorted_list_by_eid(&config.dst_configs, local_eid)?;
+    let total_dst_amount = requests
+        .iter()
+        .fold(value as u128, |total, request| total + request.amount as u128);
+    require!(total_dst_amount <= dst_config.native_drop_cap, ExecutorError::NativeAmountExceedsCap);
+    Ok(())
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ExecuteWithNativeDropParams {
+    pub native_drop_requests: Vec<NativeDropRequest>,
+    pub execute: ExecuteParams,
+}
+
+#[test]
+fn native_drop_cap_of_the_endpoint_eid() {
+    use anchor_lang::system_program;
+    use oapp::endpoint;
+    use std::collections::BTreeSet;
+    use utils::appended_field::Appended;
+
+    let local_eid = 30168;
+    let executor = Pubkey::new_unique();
+    let dst_config = |eid, native_drop_cap| DstConfig {
+        eid,
+        lz_receive_base_gas: 0,
+        lz_compose_base_gas: 0,
+        multiplier_bps: None,
+        floor_margin_usd: None,
+        native_drop_cap,
+    };
+    let (config_key, config_bump) = Pubkey::find_program_address(&[EXECUTOR_CONFIG_SEED], &ID);
+    let config = ExecutorConfig {
+        bump: config_bump,
+        owner: Pubkey::new_unique(),
+        acl: worker_interface::worker_utils::Acl { allow_list: vec![], deny_list: vec![] },
+        admins: vec![],
+        executors: vec![executor],
+        msglibs: vec![],
+        paused: false,
+        default_multiplier_bps: 0,
+        price_feed: Pubkey::new_unique(),
+        dst_configs: vec![dst_config(1, u128::MAX), dst_config(local_eid, 100)],
+        appended: Appended::default(),
+    };
+    let (settings_key, settings_bump) =
+        Pubkey::find_program_address(&[ENDPOINT_SEED], &endpoint::ID);
+    let settings = EndpointSettings {
+        eid: local_eid,
+        bump: settings_bump,
+        admin: Pubkey::new_unique(),
+        lz_token_mint: None,
+    };
+    let (event_authority, _) = Pubkey::find_program_address(&[b"__event_authority"], &ID);
+
+    let try_accounts = |settings_key: Pubkey| {
+        let keys = [
+            // the executor is the writable signer
+            (executor, system_program::ID, true, false),
+            (config_key, ID, false, false),
+            (endpoint::ID, Pubkey::default(), false, true),
+            (settings_key, endpoint::ID, false, false),
+            (Pubkey::new_unique(), Pubkey::default(), false, false),
+            (system_program::ID, Pubkey::default(), false, true),
+            (event_authority, Pubkey::default(), false, false),
+            (ID, Pubkey::default(), false, true),
+        ];
+        let mut lamports = vec![1u64; keys.len()];
+        let mut data = vec![vec![]; keys.len()];
+        config.try_serialize(&mut data[1]).unwrap();
+        settings.try_serialize(&mut data[3]).unwrap();
+        let infos = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|(((key, owner, signer, executable), lamports), data)| {
+                AccountInfo::new(key, *signer, *signer, lamports, data, owner, *executable, 0)
+            })
+            .collect::<Vec<_>>();
+        let mut accounts = &infos[..];
+        ExecuteWithNativeDrop::try_accounts(
+            &ID,
+            &mut accounts,
+            &[],
+            &mut ExecuteWithNativeDropBumps::default(),
+            &mut BTreeSet::new(),
+        )
+        .map(|accounts| (accounts.endpoint_settings.eid, accounts.config.into_inner()))
+    };
+
+    // the local eid is read from the endpoint settings, whose dst config caps the native drops
+    let (eid, config) = try_accounts(settings_key).unwrap();
+    assert_eq!(eid, local_eid);
+    let requests = vec![NativeDropRequest { receiver: Pubkey::new_unique(), amount: 60 }];
+    assert!(check_native_drop_cap(&config, eid, &requests, 40).is_ok());
+    let result = check_native_drop_cap(&config, eid, &requests, 41);
+    assert_eq!(result.unwrap_err(), ExecutorError::NativeAmountExceedsCap.into());
+
+    // the endpoint settings must be at the address of the endpoint
+    let result = try_accounts(Pubkey::new_unique());
+    assert!(result.err().unwrap().to_string().contains("ConstraintSeeds"));
+}
@@ -0,0 +1,138 @@
+use crate::*;
+use anchor_lang::{
+    solana_program::{program::invoke_signed, system_instruction},
+    system_program, Discriminator,
+};
+use oapp::common::{ExecutionContextV1, EXECUTION_CONTEXT_SEED};
+
+/// Initializes the execution context of the executor before an execution batch, recording the
+/// balance of the executor to cap its spend in the batch by the fee limit. The context of the
+/// previous batch must be closed by close_execution_context() first, so that the spend of a batch
+/// can not be reset in the middle of it.
+#[derive(Accounts)]
+pub struct InitExecutionContext<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [EXECUTOR_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.executors.contains(executor.key) @ExecutorError::NotExecutor
+    )]
+    pub config: Account<'info, ExecutorConfig>,
+    /// CHECK: the execution context of the executor, created by this instruction
+    #[account(mut, seeds = [EXECUTION_CONTEXT_SEED, executor.key().as_ref()], bump)]
+    pub context: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl InitExecutionContext<'_> {
+    pub fn apply(
+        ctx: &mut Context<InitExecutionContext>,
+        params: &InitExecutionContextParams,
+    ) -> Result<()> {
+        require!(ctx.accounts.context.owner.key() != ID, ExecutorError::ExecutionContextNotClosed);
+        create_execution_context(ctx)?;
+        let context = ExecutionContextV1 {
+            initial_payer_balance: ctx.accounts.executor.lamports(),
+            fee_limit: params.fee_limit,
+        };
+        let mut data = ctx.accounts.context.try_borrow_mut_data()?;
+        data[..8].copy_from_slice(&ExecutionContextV1::DISCRIMINATOR);
+        context.serialize(&mut &mut data[8..])?;
+        Ok(())
+    }
+}
+
+// creates the context account even if it holds lamports, like the init constraint of anchor
+fn create_execution_context(ctx: &Context<InitExecutionContext>) -> Result<()> {
+    let executor = ctx.accounts.executor.to_account_info();
+    let context = ctx.accounts.context.to_account_info();
+    let space = 8 + ExecutionContextV1::INIT_SPACE;
+    let seeds: &[&[u8]] = &[EXECUTION_CONTEXT_SEED, executor.key.as_ref(), &[ctx.bumps.context]];
+
+    let required_lamports = Rent::get()?.minimum_balance(space).saturating_sub(context.lamports());
+    if required_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer { from: executor.clone(), to: context.clone() },
+            ),
+            required_lamports,
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(context.key, space as u64),
+        &[context.clone()],
+        &[seeds],
+    )?;
+    invoke_signed(&system_instruction::assign(context.key, &ID), &[context], &[seeds])?;
+    Ok(())
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct InitExecutionContextParams {
+    pub fee_limit: u64,
+}
+
+#[test]
+fn cap_spend_of_execution_batch() {
+    use crate::instructions::execute_v2::ExecutionFixture;
+    use oapp::lz_receive_types_v2;
+    use std::collections::BTreeSet;
+
+    let mut fixture = ExecutionFixture::new(10_000_000);
+    let spend = fixture.spend(60);
+    let init = |fixture: &mut ExecutionFixture, fee_limit| {
+        let infos = fixture.infos();
+        let accounts = [0, 1, 2, 5].map(|i| infos[i].clone());
+        let mut bumps = InitExecutionContextBumps::default();
+        let mut init = InitExecutionContext::try_accounts(
+            &ID,
+            &mut &accounts[..],
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        )?;
+        let params = InitExecutionContextParams { fee_limit };
+        InitExecutionContext::apply(&mut Context::new(&ID, &mut init, &[], bumps), &params)
+    };
+
+    // the spend of the batch is capped by the fee limit over the executions, not by their value
+    init(&mut fixture, 100).unwrap();
+    let context_rent = Rent::default().minimum_balance(8 + ExecutionContextV1::INIT_SPACE);
+    let balance = 10_000_000 - context_rent;
+    assert_eq!(fixture.lamports[ExecutionFixture::EXECUTOR], balance);
+    fixture
+        .execute(vec![spend.clone(), ExecutionFixture::lz_receive()], 60)
+        .unwrap();
+    let result = fixture.execute(vec![spend.clone(), ExecutionFixture::lz_receive()], 60);
+    assert_eq!(result.unwrap_err(), ExecutorError::FeeLimitExceeded.into());
+
+    // the open context can not be reset, by the executor or by a plan
+    let result = init(&mut fixture, 100);
+    assert_eq!(result.unwrap_err(), ExecutorError::ExecutionContextNotClosed.into());
+    let reset = lz_receive_types_v2::Instruction::Standard {
+        program_id: ID,
+        accounts: vec![],
+        data: anchor_lang::InstructionData::data(&crate::instruction::InitExecutionContext {
+            params: InitExecutionContextParams { fee_limit: u64::MAX },
+        }),
+    };
+    let result = fixture.execute(vec![reset, ExecutionFixture::lz_receive()], 60);
+    assert_eq!(result.unwrap_err(), ExecutorError::InvalidExecutionPlan.into());
+
+    // nor closed over the fee limit, spent here by the failed execution as it is not reverted
+    let infos = fixture.infos();
+    let accounts = [0, 2].map(|i| infos[i].clone());
+    let mut bumps = CloseExecutionContextBumps::default();
+    let mut close = CloseExecutionContext::try_accounts(
+        &ID,
+        &mut &accounts[..],
+        &[],
+        &mut bumps,
+        &mut BTreeSet::new(),
+    )
+    .unwrap();
+    let result = CloseExecutionContext::apply(&mut Context::new(&ID, &mut close, &[], bumps));
+    assert_eq!(result.unwrap_err(), ExecutorError::FeeLimitExceeded.into());
+}
@@ -18,6 +18,7 @@ pub struct InitExecutor<'info> {
 impl InitExecutor<'_> {
     pub fn apply(ctx: &mut Context<InitExecutor>, params: &InitExecutorParams) -> Result<()> {
         ctx.accounts.config.bump = ctx.bumps.config;
+        ctx.accounts.config.appended.migrate();
         ctx.accounts.config.owner = params.owner;
         ctx.accounts.config.set_admins(params.admins.clone())?;
         ctx.accounts.config.set_msglibs(params.msglibs.clone())?;
@@ -0,0 +1,31 @@
+use crate::*;
+use utils::appended_field::{zero_trailing_data, Appended};
+
+/// Migrates an executor config created before the appended fields, zeroing the rest of its data so
+/// that no stale bytes are read as the appended fields
+#[derive(Accounts)]
+pub struct MigrateExecutorConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [EXECUTOR_CONFIG_SEED],
+        bump = config.bump,
+        realloc = config.to_account_info().data_len() + Appended::<ExecutorConfigAppended>::INIT_SPACE,
+        realloc::payer = admin,
+        realloc::zero = false,
+        constraint = config.admins.contains(admin.key) @ExecutorError::NotAdmin,
+        constraint = !config.appended.is_migrated() @ExecutorError::AlreadyMigrated
+    )]
+    pub config: Account<'info, ExecutorConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateExecutorConfig<'_> {
+    pub fn apply(ctx: &mut Context<MigrateExecutorConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        zero_trailing_data(&config.to_account_info(), &**config)?;
+        config.appended.migrate();
+        Ok(())
+    }
+}
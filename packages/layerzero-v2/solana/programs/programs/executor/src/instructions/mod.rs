@@ -1,17 +1,29 @@
 pub mod admin_set_config;
+pub mod close_execution_context;
 pub mod compose;
 pub mod executable;
 pub mod execute;
+pub mod execute_v2;
+pub mod execute_with_native_drop;
+pub mod init_execution_context;
 pub mod init_executor;
+pub mod migrate_executor_config;
 pub mod native_drop;
 pub mod owner_set_config;
 pub mod quote;
+pub mod token_drop;
 
 pub use admin_set_config::*;
+pub use close_execution_context::*;
 pub use compose::*;
 pub use executable::*;
 pub use execute::*;
+pub use execute_v2::*;
+pub use execute_with_native_drop::*;
+pub use init_execution_context::*;
 pub use init_executor::*;
+pub use migrate_executor_config::*;
 pub use native_drop::*;
 pub use owner_set_config::*;
 pub use quote::*;
+pub use token_drop::*;
@@ -27,25 +27,11 @@ impl NativeDrop<'_> {
             ExecutorError::InvalidNativeDropRequestsLength
         );
 
-        let mut successes: Vec<bool> = vec![];
-        for (index, request) in params.native_drop_requests.iter().enumerate() {
-            let receiver_account_info = ctx.remaining_accounts[index].to_account_info();
-            require!(
-                receiver_account_info.key() == request.receiver,
-                ExecutorError::InvalidNativeDropReceiver
-            );
-
-            let success = program::invoke(
-                &system_instruction::transfer(
-                    ctx.accounts.executor.key,
-                    &request.receiver.key(),
-                    request.amount,
-                ),
-                &[ctx.accounts.executor.to_account_info(), receiver_account_info.to_account_info()],
-            )
-            .is_ok();
-            successes.push(success);
-        }
+        let successes = apply_native_drops(
+            &ctx.accounts.executor,
+            &params.native_drop_requests,
+            ctx.remaining_accounts,
+        )?;
 
         emit_cpi!(NativeDropAppliedEvent {
             dst_eid: params.dst_eid,
@@ -61,6 +47,30 @@ impl NativeDrop<'_> {
     }
 }
 
+/// Transfers the native drops from the executor to the receiver accounts in the same order,
+/// returning whether each transfer succeeded
+pub(crate) fn apply_native_drops<'info>(
+    executor: &AccountInfo<'info>,
+    requests: &[NativeDropRequest],
+    receivers: &[AccountInfo<'info>],
+) -> Result<Vec<bool>> {
+    let mut successes: Vec<bool> = vec![];
+    for (request, receiver_account_info) in requests.iter().zip(receivers) {
+        require!(
+            receiver_account_info.key() == request.receiver,
+            ExecutorError::InvalidNativeDropReceiver
+        );
+
+        let success = program::invoke(
+            &system_instruction::transfer(executor.key, &request.receiver.key(), request.amount),
+            &[executor.to_account_info(), receiver_account_info.to_account_info()],
+        )
+        .is_ok();
+        successes.push(success);
+    }
+    Ok(successes)
+}
+
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct NativeDropParams {
     pub src_eid: u32,
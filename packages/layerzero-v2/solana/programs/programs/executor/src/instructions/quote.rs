@@ -2,7 +2,7 @@ use crate::*;
 use pricefeed::instructions::GetFeeParams;
 use std::collections::HashMap;
 use utils::sorted_list_helper;
-use worker_interface::{worker_utils, QuoteExecutorParams};
+use worker_interface::{worker_utils, QuoteExecutorParams, TokenFee};
 
 #[derive(Accounts)]
 #[instruction(params: QuoteExecutorParams)]
@@ -49,6 +49,10 @@ impl Quote<'_> {
                     let (amount, _) = decode_native_drop_params(&option.params)?;
                     total_dst_amount += amount;
                 },
+                OPTION_TYPE_TOKEN_DROP => {
+                    total_dst_amount +=
+                        token_drop_native_amount(config, params.dst_eid, &option.params)?;
+                },
                 OPTION_TYPE_LZCOMPOSE => {
                     let (index, gas, value) = decode_lz_compose_params(&option.params)?;
 
@@ -120,4 +124,68 @@ impl Quote<'_> {
         let total_fee = fee_for_gas + fee_for_amount;
         Ok(worker_utils::safe_convert_u128_to_u64(total_fee)?)
     }
+
+    pub fn apply_in_token(
+        ctx: &Context<Quote>,
+        params: &QuoteExecutorParams,
+    ) -> Result<Option<TokenFee>> {
+        match &ctx.accounts.executor_config.appended.fee_token {
+            Some(fee_token) => Ok(Some(fee_token.convert(Self::apply(ctx, params)?)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+// the token drop is quoted as the native drop of its amount converted by the token drop config
+fn token_drop_native_amount(config: &ExecutorConfig, dst_eid: u32, params: &[u8]) -> Result<u128> {
+    let (amount, mint, _) = decode_token_drop_params(params)?;
+    let token_drop_config = sorted_list_helper::get_from_sorted_list_by_eid(
+        &config.appended.dst_token_drop_configs,
+        dst_eid,
+    )?;
+    require!(token_drop_config.mint == mint, ExecutorError::InvalidTokenDropMint);
+    token_drop_config.to_native_amount(amount)
+}
+
+#[test]
+fn quote_token_drop_as_native_amount() {
+    use utils::appended_field::Appended;
+
+    let (mint, receiver) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let token_drop = |amount: u128, mint: Pubkey| {
+        [&amount.to_be_bytes()[..], mint.as_ref(), receiver.as_ref()].concat()
+    };
+    // 1 token unit is worth 0.5 native token unit
+    let price_ratio = TOKEN_DROP_PRICE_RATIO_DENOMINATOR / 2;
+    let config = ExecutorConfig {
+        bump: 0,
+        owner: Pubkey::new_unique(),
+        acl: worker_utils::Acl { allow_list: vec![], deny_list: vec![] },
+        admins: vec![],
+        executors: vec![],
+        msglibs: vec![],
+        paused: false,
+        default_multiplier_bps: 0,
+        price_feed: Pubkey::new_unique(),
+        dst_configs: vec![],
+        appended: Appended::new(ExecutorConfigAppended {
+            fee_token: None,
+            dst_token_drop_configs: vec![DstTokenDropConfig {
+                eid: 1,
+                mint: mint.to_bytes(),
+                price_ratio,
+            }],
+        }),
+    };
+
+    assert_eq!(token_drop_native_amount(&config, 1, &token_drop(100, mint)).unwrap(), 50);
+    assert_eq!(token_drop_native_amount(&config, 1, &token_drop(0, mint)).unwrap(), 0);
+
+    let result = token_drop_native_amount(&config, 1, &token_drop(100, Pubkey::new_unique()));
+    assert_eq!(result.unwrap_err(), ExecutorError::InvalidTokenDropMint.into());
+    assert!(token_drop_native_amount(&config, 2, &token_drop(100, mint)).is_err());
+
+    // the conversion overflowing exceeds the native drop cap
+    let result = token_drop_native_amount(&config, 1, &token_drop(u128::MAX, mint));
+    assert_eq!(result.unwrap_err(), ExecutorError::NativeAmountExceedsCap.into());
 }
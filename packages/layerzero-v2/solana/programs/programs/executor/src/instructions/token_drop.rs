@@ -0,0 +1,141 @@
+use crate::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+/// Drops the token of the mint from the token account of the executor config to the associated
+/// token account of the receiver, which is created if needed at the expense of the executor. The
+/// amount, the mint and the receiver are those of the token drop option of the packet. The
+/// inventory is owned by the config, and this instruction can not be called by the instructions
+/// of an execution plan, which execute_v2() does not send to this program.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TokenDrop<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    #[account(
+        seeds = [EXECUTOR_CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.executors.contains(executor.key) @ExecutorError::NotExecutor
+    )]
+    pub config: Account<'info, ExecutorConfig>,
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = config,
+        token::token_program = token_program
+    )]
+    pub config_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: the wallet address to receive the token, checked against the option
+    pub receiver: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = executor,
+        associated_token::mint = mint,
+        associated_token::authority = receiver,
+        associated_token::token_program = token_program
+    )]
+    pub receiver_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl TokenDrop<'_> {
+    pub fn apply(ctx: &mut Context<TokenDrop>, params: &TokenDropParams) -> Result<()> {
+        let (amount, mint, receiver) = decode_token_drop_option(&params.option_params)?;
+        require!(ctx.accounts.mint.key() == mint, ExecutorError::InvalidTokenDropMint);
+        require!(ctx.accounts.receiver.key() == receiver, ExecutorError::InvalidTokenDropReceiver);
+
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.config_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.receiver_token_account.to_account_info(),
+            authority: ctx.accounts.config.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                &[&[EXECUTOR_CONFIG_SEED, &[ctx.accounts.config.bump]]],
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        emit_cpi!(TokenDropAppliedEvent {
+            src_eid: params.src_eid,
+            sender: params.sender,
+            nonce: params.nonce,
+            dst_eid: params.dst_eid,
+            oapp: params.oapp,
+            mint: ctx.accounts.mint.key(),
+            receiver,
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct TokenDropParams {
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub dst_eid: u32,
+    pub oapp: Pubkey,
+    // the params of the token drop option, the amount of the mint to the receiver
+    pub option_params: Vec<u8>,
+}
+
+/// Decodes the amount, the mint and the receiver of the token drop option, the amount of which
+/// must be within the balance of a token account
+fn decode_token_drop_option(option_params: &[u8]) -> Result<(u64, Pubkey, Pubkey)> {
+    let (amount, mint, receiver) = decode_token_drop_params(option_params)?;
+    let amount = u64::try_from(amount).map_err(|_| ExecutorError::InsufficientBalance)?;
+    Ok((amount, Pubkey::from(mint), Pubkey::from(receiver)))
+}
+
+#[test]
+fn decode_token_drop_of_option() {
+    let (mint, receiver) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let option_params =
+        |amount: u128| [&amount.to_be_bytes()[..], &mint.to_bytes(), &receiver.to_bytes()].concat();
+    assert_eq!(decode_token_drop_option(&option_params(7)).unwrap(), (7, mint, receiver));
+
+    let result = decode_token_drop_option(&option_params(u64::MAX as u128 + 1));
+    assert_eq!(result.unwrap_err(), ExecutorError::InsufficientBalance.into());
+    let result = decode_token_drop_option(&option_params(7)[..79]);
+    assert_eq!(result.unwrap_err(), ExecutorError::InvalidSize.into());
+}
+
+#[test]
+fn reject_plan_instructions_to_token_drop() {
+    use crate::instructions::execute_v2::ExecutionFixture;
+    use oapp::{
+        common::{AccountMetaRef, AddressLocator},
+        lz_receive_types_v2,
+    };
+
+    // a plan can not sign the token drop of the executor to drain the inventory of the config
+    let mut fixture = ExecutionFixture::new(1_000_000);
+    let params = TokenDropParams {
+        src_eid: 1,
+        sender: [0; 32],
+        nonce: 1,
+        dst_eid: 2,
+        oapp: Pubkey::new_unique(),
+        option_params: [&7u128.to_be_bytes()[..], &[1; 32], &[2; 32]].concat(),
+    };
+    let token_drop = lz_receive_types_v2::Instruction::Standard {
+        program_id: ID,
+        accounts: vec![AccountMetaRef { pubkey: AddressLocator::Payer, is_writable: true }],
+        data: anchor_lang::InstructionData::data(&crate::instruction::TokenDrop { params }),
+    };
+    let result = fixture.execute(vec![token_drop, ExecutionFixture::lz_receive()], 0);
+    assert_eq!(result.unwrap_err(), ExecutorError::InvalidExecutionPlan.into());
+}
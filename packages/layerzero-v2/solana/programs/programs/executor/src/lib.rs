@@ -11,7 +11,7 @@ use instructions::*;
 use options_codec::*;
 use solana_helper::program_id_from_env;
 use state::*;
-use worker_interface::QuoteExecutorParams;
+use worker_interface::{QuoteExecutorParams, TokenFee};
 
 declare_id!(Pubkey::new_from_array(program_id_from_env!(
     "EXECUTOR_ID",
@@ -44,6 +44,10 @@ pub mod executor {
         AdminSetConfig::apply(&mut ctx, &params)
     }
 
+    pub fn migrate_executor_config(mut ctx: Context<MigrateExecutorConfig>) -> Result<()> {
+        MigrateExecutorConfig::apply(&mut ctx)
+    }
+
     pub fn native_drop<'c: 'info, 'info>(
         mut ctx: Context<'_, '_, 'c, 'info, NativeDrop<'info>>,
         params: NativeDropParams,
@@ -51,10 +55,36 @@ pub mod executor {
         NativeDrop::apply(&mut ctx, &params)
     }
 
+    pub fn token_drop(mut ctx: Context<TokenDrop>, params: TokenDropParams) -> Result<()> {
+        TokenDrop::apply(&mut ctx, &params)
+    }
+
     pub fn execute(mut ctx: Context<Execute>, params: ExecuteParams) -> Result<()> {
         Execute::apply(&mut ctx, &params)
     }
 
+    pub fn init_execution_context(
+        mut ctx: Context<InitExecutionContext>,
+        params: InitExecutionContextParams,
+    ) -> Result<()> {
+        InitExecutionContext::apply(&mut ctx, &params)
+    }
+
+    pub fn close_execution_context(mut ctx: Context<CloseExecutionContext>) -> Result<()> {
+        CloseExecutionContext::apply(&mut ctx)
+    }
+
+    pub fn execute_v2(mut ctx: Context<ExecuteV2>, params: ExecuteV2Params) -> Result<()> {
+        ExecuteV2::apply(&mut ctx, &params)
+    }
+
+    pub fn execute_with_native_drop<'c: 'info, 'info>(
+        mut ctx: Context<'_, '_, 'c, 'info, ExecuteWithNativeDrop<'info>>,
+        params: ExecuteWithNativeDropParams,
+    ) -> Result<()> {
+        ExecuteWithNativeDrop::apply(&mut ctx, &params)
+    }
+
     pub fn compose(mut ctx: Context<Compose>, params: ComposeParams) -> Result<()> {
         Compose::apply(&mut ctx, &params)
     }
@@ -64,6 +94,13 @@ pub mod executor {
         Quote::apply(&ctx, &params)
     }
 
+    pub fn quote_executor_in_token(
+        ctx: Context<Quote>,
+        params: QuoteExecutorParams,
+    ) -> Result<Option<TokenFee>> {
+        Quote::apply_in_token(&ctx, &params)
+    }
+
     pub fn executable(
         ctx: Context<Executable>,
         params: ExecutableParams,
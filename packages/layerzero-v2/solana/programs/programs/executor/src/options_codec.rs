@@ -5,6 +5,7 @@ pub const OPTION_TYPE_LZRECEIVE: u8 = 1;
 pub const OPTION_TYPE_NATIVE_DROP: u8 = 2;
 pub const OPTION_TYPE_LZCOMPOSE: u8 = 3;
 pub const OPTION_TYPE_ORDERED_EXECUTION: u8 = 4;
+pub const OPTION_TYPE_TOKEN_DROP: u8 = 5;
 
 pub fn decode_lz_receive_params(params: &[u8]) -> Result<(u128, u128)> {
     require!(params.len() == 16 || params.len() == 32, ExecutorError::InvalidSize);
@@ -20,6 +21,15 @@ pub fn decode_native_drop_params(params: &[u8]) -> Result<(u128, [u8; 32])> {
     Ok((amount, receiver))
 }
 
+/// Decodes the amount, the mint and the receiver of a token drop
+pub fn decode_token_drop_params(params: &[u8]) -> Result<(u128, [u8; 32], [u8; 32])> {
+    require!(params.len() == 80, ExecutorError::InvalidSize);
+    let amount = params.to_u128(0);
+    let mint = params.to_byte_array(16);
+    let receiver = params.to_byte_array(48);
+    Ok((amount, mint, receiver))
+}
+
 pub fn decode_lz_compose_params(params: &[u8]) -> Result<(u16, u128, u128)> {
     require!(params.len() == 18 || params.len() == 34, ExecutorError::InvalidSize);
     let index = params.to_u16(0);
@@ -27,3 +37,15 @@ pub fn decode_lz_compose_params(params: &[u8]) -> Result<(u16, u128, u128)> {
     let value = if params.len() == 34 { params.to_u128(18) } else { 0 };
     Ok((index, gas, value))
 }
+
+#[test]
+fn decode_token_drop() {
+    let (mint, receiver) = ([1u8; 32], [2u8; 32]);
+    let params = [&7u128.to_be_bytes()[..], &mint, &receiver].concat();
+    assert_eq!(decode_token_drop_params(&params).unwrap(), (7, mint, receiver));
+
+    for len in [0, 48, 79, 81] {
+        let result = decode_token_drop_params(&vec![0u8; len]);
+        assert_eq!(result.unwrap_err(), ExecutorError::InvalidSize.into());
+    }
+}
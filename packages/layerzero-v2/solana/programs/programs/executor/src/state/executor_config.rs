@@ -1,5 +1,8 @@
 use crate::*;
-use utils::sorted_list_helper;
+use utils::{
+    appended_field::{Appended, AppendedFields},
+    sorted_list_helper,
+};
 use worker_interface::worker_utils;
 use worker_interface::worker_utils::insert_or_remove_sorted_pubkey_list;
 
@@ -7,6 +10,8 @@ pub const ADMINS_MAX_LEN: usize = 5;
 pub const EXECUTOR_MAX_LEN: usize = 8;
 pub const MSGLIBS_MAX_LEN: usize = 10;
 pub const DST_CONFIG_MAX_LEN: usize = 140;
+pub const DST_TOKEN_DROP_CONFIG_MAX_LEN: usize = 16;
+pub const TOKEN_DROP_PRICE_RATIO_DENOMINATOR: u128 = 100_000_000_000_000_000_000; // 1e20
 
 #[account]
 #[derive(InitSpace)]
@@ -27,6 +32,22 @@ pub struct ExecutorConfig {
     pub price_feed: Pubkey,
     #[max_len(DST_CONFIG_MAX_LEN)]
     pub dst_configs: Vec<DstConfig>,
+    pub appended: Appended<ExecutorConfigAppended>,
+}
+
+/// The fields appended to the executor config after the accounts were created. They are read as
+/// the defaults until the account is migrated by migrate_executor_config().
+#[derive(InitSpace, Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct ExecutorConfigAppended {
+    // set by admin
+    pub fee_token: Option<worker_utils::FeeToken>,
+    #[max_len(DST_TOKEN_DROP_CONFIG_MAX_LEN)]
+    pub dst_token_drop_configs: Vec<DstTokenDropConfig>, // sorted by eid
+}
+
+impl AppendedFields for ExecutorConfigAppended {
+    const DISCRIMINATOR: [u8; 8] = *b"ExeCfgEx";
+    const VERSION: u8 = 1;
 }
 
 impl ExecutorConfig {
@@ -65,6 +86,34 @@ impl ExecutorConfig {
         }
         Ok(())
     }
+
+    /// The appended fields to be set, which requires the account to be migrated
+    pub fn appended_mut(&mut self) -> Result<&mut ExecutorConfigAppended> {
+        self.appended.get_mut().ok_or(ExecutorError::NotMigrated.into())
+    }
+
+    pub fn set_dst_token_drop_configs(&mut self, configs: Vec<DstTokenDropConfig>) -> Result<()> {
+        let appended = self.appended_mut()?;
+        for config in &configs {
+            sorted_list_helper::insert_or_update_sorted_list_by_eid(
+                &mut appended.dst_token_drop_configs,
+                config.clone(),
+                DST_TOKEN_DROP_CONFIG_MAX_LEN,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn remove_dst_token_drop_configs(&mut self, dst_eids: Vec<u32>) -> Result<()> {
+        let appended = self.appended_mut()?;
+        for eid in dst_eids {
+            sorted_list_helper::remove_from_sorted_list_by_eid(
+                &mut appended.dst_token_drop_configs,
+                eid,
+            )?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
@@ -82,4 +131,30 @@ impl sorted_list_helper::EID for DstConfig {
         self.eid
     }
 }
+/// The token that can be dropped on a destination, and its price as the amount of the native token
+/// of the destination per token unit, over TOKEN_DROP_PRICE_RATIO_DENOMINATOR. The amount of the
+/// token drops is converted to the native token to be quoted and capped as the native drops.
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct DstTokenDropConfig {
+    pub eid: u32,
+    pub mint: [u8; 32],
+    pub price_ratio: u128,
+}
+
+impl DstTokenDropConfig {
+    // an amount overflowing in the conversion exceeds any native drop cap
+    pub fn to_native_amount(&self, amount: u128) -> Result<u128> {
+        let amount = amount
+            .checked_mul(self.price_ratio)
+            .ok_or(ExecutorError::NativeAmountExceedsCap)?;
+        Ok(amount / TOKEN_DROP_PRICE_RATIO_DENOMINATOR)
+    }
+}
+
+impl sorted_list_helper::EID for DstTokenDropConfig {
+    fn eid(&self) -> u32 {
+        self.eid
+    }
+}
+
 utils::generate_account_size_test!(ExecutorConfig, executor_config_test);
@@ -21,6 +21,14 @@ pub mod messagelib_interface {
         Ok(MessagingFee::default())
     }
 
+    /// Returns the messaging fee with the fees of the workers and the treasury that it adds up
+    pub fn quote_detailed(
+        _ctx: Context<Interface>,
+        _params: QuoteParams,
+    ) -> Result<MessagingFeeDetail> {
+        Ok(MessagingFeeDetail::default())
+    }
+
     pub fn init_config(_ctx: Context<Interface>, _params: InitConfigParams) -> Result<()> {
         Ok(())
     }
@@ -28,6 +36,21 @@ pub mod messagelib_interface {
     pub fn set_config(_ctx: Context<Interface>, _params: SetConfigParams) -> Result<()> {
         Ok(())
     }
+
+    /// The endpoint only registers the message libs of its endpoint_version
+    pub fn version(_ctx: Context<Interface>) -> Result<Version> {
+        Ok(Version { major: 0, minor: 0, endpoint_version: 0 })
+    }
+
+    /// Returns true if the packet is verifiable by the receive config of the receiver, for the
+    /// workers to check the verification of a packet. The accounts of the receive config are in
+    /// the remaining accounts, as the message lib lays them out.
+    pub fn verifiable(
+        _ctx: Context<VerifiableInterface>,
+        _params: VerifiableParams,
+    ) -> Result<bool> {
+        Ok(false)
+    }
 }
 
 #[derive(Accounts)]
@@ -35,6 +58,17 @@ pub struct Interface<'info> {
     pub endpoint: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct VerifiableInterface<'info> {
+    pub message_lib: UncheckedAccount<'info>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct VerifiableParams {
+    pub packet_header: Vec<u8>,
+    pub payload_hash: [u8; 32],
+}
+
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct SendParams {
     pub packet: Packet,
@@ -75,6 +109,28 @@ pub struct MessagingFee {
     pub lz_token_fee: u64,
 }
 
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Default)]
+pub struct MessagingFeeDetail {
+    pub fee: MessagingFee,
+    pub executor: WorkerFee,
+    pub dvns: Vec<WorkerFee>,
+    pub treasury: Option<TreasuryFee>, // None if there is no treasury fee to pay
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Default)]
+pub struct WorkerFee {
+    pub worker: Pubkey,
+    pub fee: u64,
+    pub fee_token: Option<Pubkey>, // the mint of the spl token the fee is paid in, None if paid in lamports
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Default)]
+pub struct TreasuryFee {
+    pub treasury: Pubkey,
+    pub fee: u64,
+    pub pay_in_lz_token: bool,
+}
+
 #[derive(Clone, AnchorSerialize, AnchorDeserialize, Default)]
 pub struct MessagingReceipt {
     pub guid: [u8; 32],
@@ -103,7 +159,7 @@ pub enum MessageLibType {
     SendAndReceive,
 }
 
-#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Debug)]
 pub struct Version {
     pub major: u64,
     pub minor: u8,
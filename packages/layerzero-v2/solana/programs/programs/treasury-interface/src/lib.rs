@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+declare_id!("6NnwEuwjAkqfEkrpLAfrf1xwAHMj2G4WGTChWuNMs9tG");
+
+#[program]
+pub mod treasury_interface {
+    use super::*;
+
+    /// Returns the treasury fee in lamports, or in lz token if pay_in_lz_token is true
+    pub fn quote_treasury(
+        _ctx: Context<QuoteTreasury>,
+        _params: QuoteTreasuryParams,
+    ) -> Result<u64> {
+        Ok(0)
+    }
+}
+
+#[derive(Accounts)]
+pub struct QuoteTreasury<'info> {
+    pub treasury_config: UncheckedAccount<'info>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct QuoteTreasuryParams {
+    pub msglib: Pubkey,
+    pub dst_eid: u32,
+    pub sender: Pubkey,
+    /// the worker fee paid in lamports
+    pub worker_fee: u64,
+    pub pay_in_lz_token: bool,
+}
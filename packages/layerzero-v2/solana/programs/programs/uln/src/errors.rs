@@ -35,4 +35,26 @@ pub enum UlnError {
     ExceededU128,
     NonSigner,
     InvalidPayer,
+    TruncatedOptionHeader,
+    ZeroSizeOption,
+    OptionOverrun,
+    FeeTokenUnavailable,
+    InvalidFeeTokenMint,
+    InvalidFeeLedger,
+    TreasuryFeeOverflow,
+    InvalidTreasuryProgram,
+    InvalidDvnSet,
+    InvalidExpiry,
+    InvalidDvnSignersLen,
+    InvalidDvnSignersQuorum,
+    DuplicateDvnSigner,
+    InvalidDvnSigners,
+    InvalidWorkerMaxFees,
+    ExceededWorkerMaxFee,
+    WorkerMaxFeeUnavailable,
+    InvalidPacketHeader,
+    NotMigrated,
+    AlreadyMigrated,
+    InvalidDvnOptionSize,
+    InvalidDvnSetPage,
 }
@@ -1,4 +1,5 @@
 use crate::*;
+pub use messagelib_helper::messagelib_interface::{TreasuryFee, WorkerFee};
 use messagelib_helper::packet_v1_codec::PACKET_HEADER_SIZE;
 
 #[event]
@@ -8,17 +9,12 @@ pub struct FeesPaidEvent {
     pub treasury: Option<TreasuryFee>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct WorkerFee {
+#[event]
+pub struct FeesClaimedEvent {
     pub worker: Pubkey,
-    pub fee: u64,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct TreasuryFee {
-    pub treasury: Pubkey,
-    pub fee: u64,
-    pub pay_in_lz_token: bool,
+    pub amount: u64,
+    // the part of the amount by destination, attributed from the unclaimed fees of the shards
+    pub fees: Vec<EidFee>,
 }
 
 #[event]
@@ -55,7 +51,54 @@ pub struct ConfigSetEvent {
     pub config: Config,
 }
 
+#[event]
+pub struct DvnSetSetEvent {
+    pub dvn_set: Pubkey,
+    pub dvns: Vec<Pubkey>,
+    pub optional_dvn_threshold: u8,
+}
+
+#[event]
+pub struct DvnSetPageWrittenEvent {
+    pub dvn_set: Pubkey,
+    pub page: u8,
+    pub dvns: Vec<Pubkey>,
+}
+
+#[event]
+pub struct DvnSignersSetEvent {
+    pub dvn: Pubkey,
+    pub signers: DvnSignersParams,
+}
+
 #[event]
 pub struct TreasurySetEvent {
     pub treasury: Option<Treasury>,
 }
+
+#[event]
+pub struct TreasuryProgramSetEvent {
+    pub eid: u32,
+    pub oapp: Pubkey,
+    pub program: Option<TreasuryProgram>,
+}
+
+#[event]
+pub struct TreasuryFeeSetEvent {
+    pub fee_config: Option<TreasuryFeeConfig>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct TreasuryFeeConfig {
+    pub native_fee_bps: u64,
+    pub native_fee_bounds: TreasuryFeeBounds,
+    pub lz_token: Option<LzTokenTreasuryFeeConfig>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct LzTokenTreasuryFeeConfig {
+    pub fee: u64,
+    pub fee_bps: u64,
+    pub price_ratio: u128,
+    pub fee_bounds: TreasuryFeeBounds,
+}
@@ -38,6 +38,8 @@ impl InitDefaultConfig<'_> {
 
         ctx.accounts.send_config.bump = ctx.bumps.send_config;
         ctx.accounts.receive_config.bump = ctx.bumps.receive_config;
+        ctx.accounts.send_config.appended.migrate();
+        ctx.accounts.receive_config.appended.migrate();
 
         emit_cpi!(DefaultConfigSetEvent {
             eid: params.eid,
@@ -22,6 +22,7 @@ impl InitUln<'_> {
         ctx.accounts.uln.endpoint_program = params.endpoint_program;
         ctx.accounts.uln.admin = params.admin;
         ctx.accounts.uln.treasury = None;
+        ctx.accounts.uln.appended.migrate();
         ctx.accounts.uln.bump = ctx.bumps.uln;
         Ok(())
     }
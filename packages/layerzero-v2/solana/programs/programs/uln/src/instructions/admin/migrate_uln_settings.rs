@@ -0,0 +1,31 @@
+use crate::*;
+use utils::appended_field::{zero_trailing_data, Appended};
+
+/// Migrates the uln settings created before the appended fields, zeroing the rest of its data so
+/// that no stale bytes are read as the appended fields
+#[derive(Accounts)]
+pub struct MigrateUlnSettings<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = admin,
+        seeds = [ULN_SEED],
+        bump = uln.bump,
+        realloc = uln.to_account_info().data_len() + Appended::<UlnSettingsAppended>::INIT_SPACE,
+        realloc::payer = admin,
+        realloc::zero = false,
+        constraint = !uln.appended.is_migrated() @UlnError::AlreadyMigrated
+    )]
+    pub uln: Account<'info, UlnSettings>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateUlnSettings<'_> {
+    pub fn apply(ctx: &mut Context<MigrateUlnSettings>) -> Result<()> {
+        let uln = &mut ctx.accounts.uln;
+        zero_trailing_data(&uln.to_account_info(), &**uln)?;
+        uln.appended.migrate();
+        Ok(())
+    }
+}
@@ -1,13 +1,17 @@
 pub mod init_default_config;
 pub mod init_uln;
+pub mod migrate_uln_settings;
 pub mod set_default_config;
 pub mod set_treasury;
+pub mod set_treasury_program;
 pub mod transfer_admin;
 pub mod withdraw_rent;
 
 pub use init_default_config::*;
 pub use init_uln::*;
+pub use migrate_uln_settings::*;
 pub use set_default_config::*;
 pub use set_treasury::*;
+pub use set_treasury_program::*;
 pub use transfer_admin::*;
 pub use withdraw_rent::*;
@@ -11,20 +11,25 @@ pub struct SetTreasury<'info> {
 
 impl SetTreasury<'_> {
     pub fn apply(ctx: &mut Context<SetTreasury>, params: &SetTreasuryParams) -> Result<()> {
-        // The signer must be the admin or the treasury admin
-        let signer = ctx.accounts.signer.key();
-        if signer != ctx.accounts.uln.admin {
-            let treasury = ctx.accounts.uln.treasury.as_ref().ok_or(UlnError::Unauthorized)?;
-            require!(Some(signer) == treasury.admin, UlnError::Unauthorized);
-        }
+        ctx.accounts.uln.assert_treasury_signer(ctx.accounts.signer.key)?;
 
         if let Some(param) = &params.treasury {
             require!(param.native_fee_bps <= BPS_DENOMINATOR, UlnError::InvalidBps);
+            require!(params.fees.lz_token_fee_bps <= BPS_DENOMINATOR, UlnError::InvalidBps);
+            params.fees.native_fee_bounds.sanity_check()?;
+            params.fees.lz_token_fee_bounds.sanity_check()?;
         }
-        ctx.accounts.uln.treasury = params.treasury.clone();
+        let uln = &mut ctx.accounts.uln;
+        let old_fee_config = uln.treasury.as_ref().map(|t| t.fee_config(uln.treasury_fees()));
+        uln.set_treasury(params.treasury.clone(), params.fees.clone())?;
 
         emit_cpi!(TreasurySetEvent { treasury: params.treasury.clone() });
 
+        let fee_config = uln.treasury.as_ref().map(|t| t.fee_config(uln.treasury_fees()));
+        if fee_config != old_fee_config {
+            emit_cpi!(TreasuryFeeSetEvent { fee_config });
+        }
+
         Ok(())
     }
 }
@@ -32,4 +37,6 @@ impl SetTreasury<'_> {
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct SetTreasuryParams {
     pub treasury: Option<Treasury>,
+    /// The fees of the treasury, which require the uln settings to be migrated unless the defaults
+    pub fees: TreasuryFees,
 }
@@ -0,0 +1,43 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: SetTreasuryProgramParams)]
+pub struct SetTreasuryProgram<'info> {
+    /// The admin or treasury admin
+    pub signer: Signer<'info>,
+    #[account(seeds = [ULN_SEED], bump = uln.bump)]
+    pub uln: Account<'info, UlnSettings>,
+    #[account(
+        mut,
+        seeds = [SEND_CONFIG_SEED, &params.eid.to_be_bytes(), &params.oapp.to_bytes()],
+        bump = send_config.bump
+    )]
+    pub send_config: Account<'info, SendConfig>,
+}
+
+impl SetTreasuryProgram<'_> {
+    pub fn apply(
+        ctx: &mut Context<SetTreasuryProgram>,
+        params: &SetTreasuryProgramParams,
+    ) -> Result<()> {
+        ctx.accounts.uln.assert_treasury_signer(ctx.accounts.signer.key)?;
+        ctx.accounts.send_config.set_treasury_program(params.program.clone())?;
+
+        emit_cpi!(TreasuryProgramSetEvent {
+            eid: params.eid,
+            oapp: params.oapp,
+            program: params.program.clone(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetTreasuryProgramParams {
+    pub eid: u32,
+    pub oapp: Pubkey,
+    /// The treasury program quoted for the fee of the oapp, whose accounts are then passed before
+    /// the other remaining accounts of quote and send
+    pub program: Option<TreasuryProgram>,
+}
@@ -12,7 +12,9 @@ pub struct WithdrawRent<'info> {
 
 impl WithdrawRent<'_> {
     pub fn apply(ctx: &mut Context<WithdrawRent>, params: &WithdrawRentParams) -> Result<()> {
-        let required_lamports = Rent::get()?.minimum_balance(8 + UlnSettings::INIT_SPACE);
+        // the uln settings may have been created with a smaller size before the appended fields
+        let data_len = ctx.accounts.uln.to_account_info().data_len();
+        let required_lamports = Rent::get()?.minimum_balance(data_len);
         let surplus_lamports = ctx.accounts.uln.get_lamports() - required_lamports;
         require!(params.amount <= surplus_lamports, UlnError::InvalidAmount);
 
@@ -32,9 +32,6 @@ impl CommitVerification<'_> {
         ctx: &mut Context<CommitVerification>,
         params: &CommitVerificationParams,
     ) -> Result<()> {
-        let config =
-            get_receive_config(&ctx.accounts.receive_config, &ctx.accounts.default_receive_config)?;
-
         // assert packet header
         require!(
             packet_v1_codec::version(&params.packet_header) == PACKET_VERSION,
@@ -45,15 +42,24 @@ impl CommitVerification<'_> {
             UlnError::InvalidEid
         );
 
-        let dvns_size = config.required_dvns.len() + config.optional_dvns.len();
-
-        let confirmation_accounts = &ctx.remaining_accounts[0..dvns_size];
+        // the confirmation accounts are followed by the endpoint accounts, starting with the
+        // endpoint program
+        let endpoint_program = ctx.accounts.uln.endpoint_program;
+        let confirmation_accounts_len = ctx
+            .remaining_accounts
+            .iter()
+            .position(|account| account.key() == endpoint_program)
+            .ok_or(UlnError::InvalidAccountLength)?;
+        let (confirmation_accounts, endpoint_accounts) =
+            ctx.remaining_accounts.split_at(confirmation_accounts_len);
         require!(
-            check_verifiable(
-                &config,
+            check_receive_config_verifiable(
+                &ctx.accounts.receive_config,
+                &ctx.accounts.default_receive_config,
                 confirmation_accounts,
                 &keccak256(&params.packet_header).to_bytes(),
-                &params.payload_hash
+                &params.payload_hash,
+                Clock::get()?.slot,
             )?,
             UlnError::Verifying
         );
@@ -64,17 +70,81 @@ impl CommitVerification<'_> {
             &params.packet_header,
             params.payload_hash,
             &[ULN_SEED, &[ctx.accounts.uln.bump]],
-            &ctx.remaining_accounts[dvns_size..],
+            endpoint_accounts,
         )
     }
 }
 
-fn get_receive_config(
+/// Returns true if the packet is verifiable by the receive config, or by its previous config until
+/// the timeout expires. The accounts are the dvn set account, if the config checked against
+/// references one, followed by the confirmation accounts of its dvns.
+pub fn check_receive_config_verifiable(
+    custom_config_acc: &AccountInfo,
+    default_config: &ReceiveConfig,
+    accounts: &[AccountInfo],
+    header_hash: &[u8; 32],
+    payload_hash: &[u8; 32],
+    slot: u64,
+) -> Result<bool> {
+    check_receive_config(custom_config_acc, default_config, accounts, slot, |config, accounts| {
+        check_verifiable(config, accounts, header_hash, payload_hash)
+    })
+}
+
+/// Returns true if the dvns of the receive config, or of its previous config until the timeout
+/// expires, are verified by `check`. The accounts are the dvn set account, if the config checked
+/// against references one, followed by an account for each of its dvns, which is passed to `check`.
+pub(crate) fn check_receive_config<F>(
     custom_config_acc: &AccountInfo,
     default_config: &ReceiveConfig,
-) -> Result<UlnConfig> {
+    accounts: &[AccountInfo],
+    slot: u64,
+    check: F,
+) -> Result<bool>
+where
+    F: Fn(&UlnConfig, &[AccountInfo]) -> Result<bool>,
+{
     let custom_config = local_custom_config::<ReceiveConfig>(custom_config_acc)?;
-    UlnConfig::get_config(&default_config.uln, &custom_config.uln)
+    let verifiable = check_uln_config(
+        &default_config.uln,
+        &custom_config.uln,
+        custom_config.dvn_set(),
+        accounts,
+        &check,
+    );
+    if matches!(verifiable, Ok(true)) || !custom_config.timeout().is_active(slot) {
+        return verifiable;
+    }
+
+    // the accounts may be of the previous config, so the error of the current config is only
+    // returned if the previous config is not verifiable either
+    let timeout = custom_config.timeout();
+    match check_uln_config(&default_config.uln, &timeout.uln, timeout.dvn_set, accounts, &check) {
+        Ok(true) => Ok(true),
+        _ => verifiable,
+    }
+}
+
+fn check_uln_config<F>(
+    default_config: &UlnConfig,
+    custom_config: &UlnConfig,
+    dvn_set: Option<Pubkey>,
+    accounts: &[AccountInfo],
+    check: &F,
+) -> Result<bool>
+where
+    F: Fn(&UlnConfig, &[AccountInfo]) -> Result<bool>,
+{
+    let (dvn_set, dvn_accounts) = split_dvn_set_account(dvn_set, accounts)?;
+    let mut config = UlnConfig::get_config(default_config, custom_config)?;
+    if let Some(dvn_set) = dvn_set {
+        config.apply_dvn_set(&dvn_set)?;
+    }
+    require!(
+        dvn_accounts.len() == config.required_dvns.len() + config.optional_dvns.len(),
+        UlnError::InvalidAccountLength
+    );
+    check(&config, dvn_accounts)
 }
 
 pub fn check_verifiable(
@@ -83,10 +153,22 @@ pub fn check_verifiable(
     header_hash: &[u8; 32],
     payload_hash: &[u8; 32],
 ) -> Result<bool> {
+    check_dvns(config, |i, dvn| {
+        verified(dvn, &accounts[i], header_hash, payload_hash, config.confirmations)
+    })
+}
+
+/// Returns true if the required dvns and the threshold of the optional dvns are verified by
+/// `verified`, which is called with the index of the dvn in the required dvns followed by the
+/// optional dvns.
+pub(crate) fn check_dvns<F>(config: &UlnConfig, mut verified: F) -> Result<bool>
+where
+    F: FnMut(usize, &Pubkey) -> Result<bool>,
+{
     // iterate the required DVNs
     if config.required_dvn_count > 0 {
         for (i, dvn) in config.required_dvns.iter().enumerate() {
-            if !verified(dvn, &accounts[i], header_hash, payload_hash, config.confirmations)? {
+            if !verified(i, dvn)? {
                 return Ok(false);
             }
         }
@@ -101,13 +183,7 @@ pub fn check_verifiable(
     let mut threshold = config.optional_dvn_threshold as usize;
     let optional_acc_offset = config.required_dvns.len();
     for (i, dvn) in config.optional_dvns.iter().enumerate() {
-        if verified(
-            dvn,
-            &accounts[optional_acc_offset + i],
-            header_hash,
-            payload_hash,
-            config.confirmations,
-        )? {
+        if verified(optional_acc_offset + i, dvn)? {
             // increment the optional count if the optional DVN has signed
             threshold -= 1;
             if threshold == 0 {
@@ -162,6 +238,7 @@ pub struct CommitVerificationParams {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::appended_field::Appended;
 
     #[test]
     fn test_verified() {
@@ -434,4 +511,110 @@ mod tests {
         let result = check_verifiable(&config, &accounts, &header_hash, &payload_hash);
         assert_eq!(result, Ok(true));
     }
+
+    #[test]
+    fn test_check_receive_config_verifiable_with_timeout() {
+        let header_hash = [1u8; 32];
+        let payload_hash = [2u8; 32];
+        let uln_config = |dvn: Pubkey| UlnConfig {
+            confirmations: 3,
+            required_dvn_count: 1,
+            optional_dvn_count: 0,
+            optional_dvn_threshold: 0,
+            required_dvns: vec![dvn],
+            optional_dvns: vec![],
+        };
+
+        // the receive config changed from dvn1 to dvn2, keeping dvn1 until slot 100
+        let dvn1 = Pubkey::new_unique();
+        let dvn2 = Pubkey::new_unique();
+        let receive_config = ReceiveConfig {
+            bump: 0,
+            uln: uln_config(dvn2),
+            appended: Appended::new(ReceiveConfigAppended {
+                dvn_set: None,
+                timeout: UlnConfigTimeout { uln: uln_config(dvn1), dvn_set: None, expiry: 100 },
+            }),
+        };
+        let mut config_data = vec![];
+        receive_config.try_serialize(&mut config_data).unwrap();
+        let config_key = Pubkey::new_unique();
+        let lamports: &mut u64 = &mut 0;
+        let config_acc = AccountInfo::new(
+            &config_key,
+            false,
+            false,
+            lamports,
+            &mut config_data[..],
+            &ID,
+            false,
+            0,
+        );
+
+        // only dvn1 signed
+        let (acc_key1, bump_seed1) = Pubkey::find_program_address(
+            &[CONFIRMATIONS_SEED, &header_hash[..], &payload_hash[..], &dvn1.to_bytes()],
+            &ID,
+        );
+        let dvn_confirmations = Confirmations { value: Some(3), bump: bump_seed1 };
+        let mut data = vec![];
+        Confirmations::try_serialize(&dvn_confirmations, &mut data).unwrap();
+        let lamports: &mut u64 = &mut 0;
+        let conf_acc1 =
+            AccountInfo::new(&acc_key1, false, false, lamports, &mut data[..], &ID, false, 0);
+        let accounts = vec![conf_acc1];
+
+        // True, verifiable by the previous config before the expiry
+        let result = check_receive_config_verifiable(
+            &config_acc,
+            &ReceiveConfig::default(),
+            &accounts,
+            &header_hash,
+            &payload_hash,
+            99,
+        );
+        assert_eq!(result, Ok(true));
+
+        // Failure, the confirmation account is not of the current config after the expiry
+        let result = check_receive_config_verifiable(
+            &config_acc,
+            &ReceiveConfig::default(),
+            &accounts,
+            &header_hash,
+            &payload_hash,
+            100,
+        );
+        assert_eq!(result.unwrap_err(), UlnError::InvalidConfirmation.into());
+    }
+
+    #[test]
+    fn test_grace_period_timeout() {
+        let dvn = Pubkey::new_unique();
+        let receive_config = ReceiveConfig {
+            bump: 0,
+            uln: UlnConfig {
+                confirmations: 3,
+                required_dvn_count: 1,
+                optional_dvn_count: 0,
+                optional_dvn_threshold: 0,
+                required_dvns: vec![dvn],
+                optional_dvns: vec![],
+            },
+            appended: Appended::default(),
+        };
+
+        // the current config keeps verifying for the grace period
+        let timeout = receive_config.grace_period_timeout(10, 100).unwrap();
+        assert_eq!(timeout.expiry, 110);
+        assert_eq!(timeout.uln.required_dvns, vec![dvn]);
+        assert_eq!(receive_config.grace_period_timeout(0, 100).unwrap().expiry, 0);
+
+        // a grace period overflowing the slot is rejected
+        let result = receive_config.grace_period_timeout(u64::MAX, 100).map(|t| t.expiry);
+        assert_eq!(result.unwrap_err(), UlnError::InvalidExpiry.into());
+        assert_eq!(
+            receive_config.grace_period_timeout(u64::MAX - 100, 100).unwrap().expiry,
+            u64::MAX
+        );
+    }
 }
@@ -0,0 +1,207 @@
+use crate::*;
+use anchor_lang::solana_program::sysvar;
+use messagelib_helper::{
+    endpoint_verify,
+    packet_v1_codec::{self, PACKET_HEADER_SIZE},
+};
+use utils::ed25519::verified_ed25519_signatures;
+
+/// Commits the verification of a packet with the signatures of the dvn signers, instead of the
+/// confirmations accounts written by each dvn in its own transactions. The ed25519 signatures are
+/// verified by the ed25519 program instructions of the same transaction.
+#[derive(Accounts)]
+#[instruction(params: CommitVerificationWithSignaturesParams)]
+pub struct CommitVerificationWithSignatures<'info> {
+    /// The custom receive config account may be uninitialized, so deserialize it only if it's initialized
+    #[account(
+        seeds = [
+            RECEIVE_CONFIG_SEED,
+            &packet_v1_codec::src_eid(&params.packet_header).to_be_bytes(),
+            &packet_v1_codec::receiver_pubkey(&params.packet_header).to_bytes()
+        ],
+        bump
+    )]
+    pub receive_config: AccountInfo<'info>,
+    #[account(
+        seeds = [RECEIVE_CONFIG_SEED, &packet_v1_codec::src_eid(&params.packet_header).to_be_bytes()],
+        bump = default_receive_config.bump,
+    )]
+    pub default_receive_config: Account<'info, ReceiveConfig>,
+    #[account(seeds = [ULN_SEED], bump = uln.bump)]
+    pub uln: Account<'info, UlnSettings>,
+    /// CHECK: the instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+impl CommitVerificationWithSignatures<'_> {
+    pub fn apply(
+        ctx: &mut Context<CommitVerificationWithSignatures>,
+        params: &CommitVerificationWithSignaturesParams,
+    ) -> Result<()> {
+        // assert packet header
+        require!(
+            packet_v1_codec::version(&params.packet_header) == PACKET_VERSION,
+            UlnError::InvalidPacketVersion
+        );
+        require!(
+            packet_v1_codec::dst_eid(&params.packet_header) == ctx.accounts.uln.eid,
+            UlnError::InvalidEid
+        );
+
+        // the dvn signers accounts are followed by the endpoint accounts, starting with the
+        // endpoint program
+        let endpoint_program = ctx.accounts.uln.endpoint_program;
+        let signers_accounts_len = ctx
+            .remaining_accounts
+            .iter()
+            .position(|account| account.key() == endpoint_program)
+            .ok_or(UlnError::InvalidAccountLength)?;
+        let (signers_accounts, endpoint_accounts) =
+            ctx.remaining_accounts.split_at(signers_accounts_len);
+
+        let ed25519_signatures = verified_ed25519_signatures(&ctx.accounts.instructions_sysvar)?;
+        require!(
+            check_receive_config(
+                &ctx.accounts.receive_config,
+                &ctx.accounts.default_receive_config,
+                signers_accounts,
+                Clock::get()?.slot,
+                |config, accounts| {
+                    require!(
+                        params.attestations.len() == accounts.len(),
+                        UlnError::InvalidAccountLength
+                    );
+                    check_dvns(config, |i, dvn| {
+                        signed(
+                            dvn,
+                            &accounts[i],
+                            &params.attestations[i],
+                            &params.packet_header,
+                            &params.payload_hash,
+                            &ed25519_signatures,
+                            config.confirmations,
+                        )
+                    })
+                },
+            )?,
+            UlnError::Verifying
+        );
+
+        endpoint_verify::verify(
+            ctx.accounts.uln.endpoint_program,
+            ctx.accounts.uln.key(),
+            &params.packet_header,
+            params.payload_hash,
+            &[ULN_SEED, &[ctx.accounts.uln.bump]],
+            endpoint_accounts,
+        )
+    }
+}
+
+/// Returns true if the quorum of the signers of the dvn signed the packet with the required
+/// confirmations. The dvns without a signers account are not verified.
+fn signed(
+    dvn: &Pubkey,
+    signers_account: &AccountInfo,
+    attestation: &DvnAttestation,
+    packet_header: &[u8],
+    payload_hash: &[u8; 32],
+    ed25519_signatures: &[(Pubkey, Vec<u8>)],
+    required_conf: u64,
+) -> Result<bool> {
+    if signers_account.owner.key() != ID || attestation.confirmations < required_conf {
+        return Ok(false);
+    }
+    let mut data: &[u8] = &signers_account.try_borrow_data()?;
+    let dvn_signers = DvnSigners::try_deserialize(&mut data)?;
+
+    let expected_address = Pubkey::create_program_address(
+        &[DVN_SIGNERS_SEED, &dvn.to_bytes(), &[dvn_signers.bump]],
+        &ID,
+    )
+    .map_err(|_| UlnError::InvalidDvnSigners)?;
+    require!(signers_account.key() == expected_address, UlnError::InvalidDvnSigners);
+
+    let hash = verification_hash(dvn, packet_header, payload_hash, attestation.confirmations);
+    Ok(dvn_signers.signed(&hash, &attestation.signatures, ed25519_signatures))
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CommitVerificationWithSignaturesParams {
+    pub packet_header: [u8; PACKET_HEADER_SIZE],
+    pub payload_hash: [u8; 32],
+    /// the attestation of each dvn of the config, in the order of the dvn signers accounts
+    pub attestations: Vec<DvnAttestation>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct DvnAttestation {
+    pub confirmations: u64,
+    /// the secp256k1 signatures of the verification hash, without the ed25519 signatures that are
+    /// verified by the ed25519 program
+    pub signatures: Vec<[u8; SECP256K1_SIGNATURE_BYTES]>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed() {
+        let packet_header = [1u8; PACKET_HEADER_SIZE];
+        let payload_hash = [2u8; 32];
+        let dvn = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let (signers_key, bump) =
+            Pubkey::find_program_address(&[DVN_SIGNERS_SEED, &dvn.to_bytes()], &ID);
+        let dvn_signers = DvnSigners {
+            dvn,
+            bump,
+            quorum: 1,
+            secp256k1_signers: vec![],
+            ed25519_signers: vec![signer],
+        };
+        let mut data = vec![];
+        dvn_signers.try_serialize(&mut data).unwrap();
+        let lamports: &mut u64 = &mut 0;
+        let signers_account =
+            AccountInfo::new(&signers_key, false, false, lamports, &mut data[..], &ID, false, 0);
+
+        let attestation = DvnAttestation { confirmations: 3, signatures: vec![] };
+        let hash = verification_hash(&dvn, &packet_header, &payload_hash, 3);
+        let ed25519_signatures = vec![(signer, hash.to_vec())];
+        let signed_with = |attestation: &DvnAttestation, required_conf: u64| {
+            signed(
+                &dvn,
+                &signers_account,
+                attestation,
+                &packet_header,
+                &payload_hash,
+                &ed25519_signatures,
+                required_conf,
+            )
+        };
+
+        // signed with the required confirmations
+        assert_eq!(signed_with(&attestation, 3), Ok(true));
+        // not signed, fewer confirmations than required
+        assert_eq!(signed_with(&attestation, 4), Ok(false));
+        // not signed, the signature is of other confirmations
+        let attestation = DvnAttestation { confirmations: 4, signatures: vec![] };
+        assert_eq!(signed_with(&attestation, 3), Ok(false));
+
+        // failure, the signers account is of another dvn
+        let attestation = DvnAttestation { confirmations: 3, signatures: vec![] };
+        let result = signed(
+            &Pubkey::new_unique(),
+            &signers_account,
+            &attestation,
+            &packet_header,
+            &payload_hash,
+            &ed25519_signatures,
+            3,
+        );
+        assert_eq!(result.unwrap_err(), UlnError::InvalidDvnSigners.into());
+    }
+}
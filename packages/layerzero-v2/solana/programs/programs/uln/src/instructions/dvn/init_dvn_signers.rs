@@ -0,0 +1,38 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: InitDvnSignersParams)]
+pub struct InitDvnSigners<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub dvn: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DvnSigners::INIT_SPACE,
+        seeds = [DVN_SIGNERS_SEED, dvn.key.as_ref()],
+        bump
+    )]
+    pub dvn_signers: Account<'info, DvnSigners>,
+    pub system_program: Program<'info, System>,
+}
+
+impl InitDvnSigners<'_> {
+    pub fn apply(ctx: &mut Context<InitDvnSigners>, params: &InitDvnSignersParams) -> Result<()> {
+        ctx.accounts.dvn_signers.dvn = ctx.accounts.dvn.key();
+        ctx.accounts.dvn_signers.bump = ctx.bumps.dvn_signers;
+        ctx.accounts.dvn_signers.set(params.signers.clone())?;
+
+        emit_cpi!(DvnSignersSetEvent {
+            dvn: ctx.accounts.dvn.key(),
+            signers: params.signers.clone(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct InitDvnSignersParams {
+    pub signers: DvnSignersParams,
+}
@@ -1,9 +1,17 @@
 pub mod close_verify;
 pub mod commit_verification;
+pub mod commit_verification_with_signatures;
+pub mod init_dvn_signers;
 pub mod init_verify;
+pub mod set_dvn_signers;
+pub mod verifiable;
 pub mod verify;
 
 pub use close_verify::*;
 pub use commit_verification::*;
+pub use commit_verification_with_signatures::*;
+pub use init_dvn_signers::*;
 pub use init_verify::*;
+pub use set_dvn_signers::*;
+pub use verifiable::*;
 pub use verify::*;
@@ -0,0 +1,30 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetDvnSigners<'info> {
+    pub dvn: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [DVN_SIGNERS_SEED, dvn.key.as_ref()],
+        bump = dvn_signers.bump
+    )]
+    pub dvn_signers: Account<'info, DvnSigners>,
+}
+
+impl SetDvnSigners<'_> {
+    pub fn apply(ctx: &mut Context<SetDvnSigners>, params: &SetDvnSignersParams) -> Result<()> {
+        ctx.accounts.dvn_signers.set(params.signers.clone())?;
+
+        emit_cpi!(DvnSignersSetEvent {
+            dvn: ctx.accounts.dvn.key(),
+            signers: params.signers.clone(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetDvnSignersParams {
+    pub signers: DvnSignersParams,
+}
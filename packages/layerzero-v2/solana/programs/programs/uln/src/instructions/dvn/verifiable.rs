@@ -0,0 +1,61 @@
+use crate::*;
+use anchor_lang::solana_program::keccak::hash as keccak256;
+use messagelib_helper::{
+    messagelib_interface::VerifiableParams,
+    packet_v1_codec::{self, PACKET_HEADER_SIZE},
+};
+
+/// The view of the verifiable interface of the message libs. The remaining accounts are the
+/// receive config and the default receive config of the packet, followed by the dvn set account
+/// if the config references one, and the confirmation accounts of its dvns.
+#[derive(Accounts)]
+pub struct Verifiable<'info> {
+    #[account(seeds = [ULN_SEED], bump = uln.bump)]
+    pub uln: Account<'info, UlnSettings>,
+}
+
+impl Verifiable<'_> {
+    pub fn apply(ctx: &Context<Verifiable>, params: &VerifiableParams) -> Result<bool> {
+        require!(params.packet_header.len() == PACKET_HEADER_SIZE, UlnError::InvalidPacketHeader);
+        require!(
+            packet_v1_codec::version(&params.packet_header) == PACKET_VERSION,
+            UlnError::InvalidPacketVersion
+        );
+        require!(
+            packet_v1_codec::dst_eid(&params.packet_header) == ctx.accounts.uln.eid,
+            UlnError::InvalidEid
+        );
+        require!(ctx.remaining_accounts.len() >= 2, UlnError::InvalidAccountLength);
+
+        let src_eid = packet_v1_codec::src_eid(&params.packet_header).to_be_bytes();
+        let receiver = packet_v1_codec::receiver_pubkey(&params.packet_header);
+        let (receive_config, _) = Pubkey::find_program_address(
+            &[RECEIVE_CONFIG_SEED, &src_eid, &receiver.to_bytes()],
+            &ID,
+        );
+        require!(ctx.remaining_accounts[0].key() == receive_config, ErrorCode::ConstraintSeeds);
+        let default_receive_config_acc = &ctx.remaining_accounts[1];
+        require!(default_receive_config_acc.owner == &ID, ErrorCode::AccountOwnedByWrongProgram);
+        let default_receive_config = ReceiveConfig::try_deserialize(
+            &mut &default_receive_config_acc.try_borrow_data()?[..],
+        )?;
+        let default_receive_config_address = Pubkey::create_program_address(
+            &[RECEIVE_CONFIG_SEED, &src_eid, &[default_receive_config.bump]],
+            &ID,
+        )
+        .map_err(|_| ErrorCode::ConstraintSeeds)?;
+        require!(
+            default_receive_config_acc.key() == default_receive_config_address,
+            ErrorCode::ConstraintSeeds
+        );
+
+        check_receive_config_verifiable(
+            &ctx.remaining_accounts[0],
+            &default_receive_config,
+            &ctx.remaining_accounts[2..],
+            &keccak256(&params.packet_header).to_bytes(),
+            &params.payload_hash,
+            Clock::get()?.slot,
+        )
+    }
+}
@@ -0,0 +1,32 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: InitDvnSetParams)]
+pub struct InitDvnSet<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DvnSet::INIT_SPACE,
+        seeds = [DVN_SET_SEED, &authority.key().to_bytes(), &params.id.to_be_bytes()],
+        bump
+    )]
+    pub dvn_set: Account<'info, DvnSet>,
+    pub system_program: Program<'info, System>,
+}
+
+impl InitDvnSet<'_> {
+    pub fn apply(ctx: &mut Context<InitDvnSet>, params: &InitDvnSetParams) -> Result<()> {
+        ctx.accounts.dvn_set.authority = ctx.accounts.authority.key();
+        ctx.accounts.dvn_set.id = params.id;
+        ctx.accounts.dvn_set.bump = ctx.bumps.dvn_set;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct InitDvnSetParams {
+    pub id: u32,
+}
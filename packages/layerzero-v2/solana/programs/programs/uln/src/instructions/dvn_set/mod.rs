@@ -0,0 +1,7 @@
+pub mod init_dvn_set;
+pub mod set_dvn_set;
+pub mod write_dvn_set_page;
+
+pub use init_dvn_set::*;
+pub use set_dvn_set::*;
+pub use write_dvn_set_page::*;
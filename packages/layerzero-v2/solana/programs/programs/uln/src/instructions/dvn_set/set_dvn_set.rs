@@ -0,0 +1,34 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetDvnSet<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = authority @UlnError::Unauthorized,
+        seeds = [DVN_SET_SEED, &authority.key().to_bytes(), &dvn_set.id.to_be_bytes()],
+        bump = dvn_set.bump
+    )]
+    pub dvn_set: Account<'info, DvnSet>,
+}
+
+impl SetDvnSet<'_> {
+    /// Replaces the dvns of the set with the pages written by write_dvn_set_page()
+    pub fn apply(ctx: &mut Context<SetDvnSet>, params: &SetDvnSetParams) -> Result<()> {
+        let dvn_set = &mut ctx.accounts.dvn_set;
+        dvn_set.set_written_dvns(params.optional_dvn_threshold)?;
+
+        emit_cpi!(DvnSetSetEvent {
+            dvn_set: dvn_set.key(),
+            dvns: dvn_set.dvns.clone(),
+            optional_dvn_threshold: params.optional_dvn_threshold,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetDvnSetParams {
+    pub optional_dvn_threshold: u8,
+}
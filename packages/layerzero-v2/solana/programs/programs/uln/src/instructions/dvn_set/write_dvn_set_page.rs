@@ -0,0 +1,34 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WriteDvnSetPage<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = authority @UlnError::Unauthorized,
+        seeds = [DVN_SET_SEED, &authority.key().to_bytes(), &dvn_set.id.to_be_bytes()],
+        bump = dvn_set.bump
+    )]
+    pub dvn_set: Account<'info, DvnSet>,
+}
+
+impl WriteDvnSetPage<'_> {
+    pub fn apply(ctx: &mut Context<WriteDvnSetPage>, params: &WriteDvnSetPageParams) -> Result<()> {
+        let dvn_set = &mut ctx.accounts.dvn_set;
+        dvn_set.write_page(params.page, &params.dvns)?;
+
+        emit_cpi!(DvnSetPageWrittenEvent {
+            dvn_set: dvn_set.key(),
+            page: params.page,
+            dvns: params.dvns.clone(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct WriteDvnSetPageParams {
+    pub page: u8,
+    pub dvns: Vec<Pubkey>, // sorted, DVN_SET_PAGE_LEN unless it is the last page
+}
@@ -0,0 +1,53 @@
+use crate::*;
+use utils::appended_field::zero_trailing_data;
+
+/// Anyone can pay to extend the config accounts of an oapp created before the dvn set and the
+/// receive config timeout were added, so that they fit the config with the most dvns. The config
+/// accounts are migrated to their appended fields.
+#[derive(Accounts)]
+#[instruction(params: ExtendConfigParams)]
+pub struct ExtendConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [SEND_CONFIG_SEED, &params.eid.to_be_bytes(), &params.oapp.to_bytes()],
+        bump = send_config.bump,
+        realloc = 8 + SendConfig::INIT_SPACE,
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub send_config: Account<'info, SendConfig>,
+    #[account(
+        mut,
+        seeds = [RECEIVE_CONFIG_SEED, &params.eid.to_be_bytes(), &params.oapp.to_bytes()],
+        bump = receive_config.bump,
+        realloc = 8 + ReceiveConfig::INIT_SPACE,
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub receive_config: Account<'info, ReceiveConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ExtendConfig<'_> {
+    pub fn apply(ctx: &mut Context<ExtendConfig>, _params: &ExtendConfigParams) -> Result<()> {
+        let send_config = &mut ctx.accounts.send_config;
+        if !send_config.appended.is_migrated() {
+            zero_trailing_data(&send_config.to_account_info(), &**send_config)?;
+            send_config.appended.migrate();
+        }
+        let receive_config = &mut ctx.accounts.receive_config;
+        if !receive_config.appended.is_migrated() {
+            zero_trailing_data(&receive_config.to_account_info(), &**receive_config)?;
+            receive_config.appended.migrate();
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ExtendConfigParams {
+    pub eid: u32,
+    pub oapp: Pubkey,
+}
@@ -30,7 +30,9 @@ pub struct InitConfig<'info> {
 impl InitConfig<'_> {
     pub fn apply(ctx: &mut Context<InitConfig>, _params: &InitConfigParams) -> Result<()> {
         ctx.accounts.send_config.bump = ctx.bumps.send_config;
+        ctx.accounts.send_config.appended.migrate();
         ctx.accounts.receive_config.bump = ctx.bumps.receive_config;
+        ctx.accounts.receive_config.appended.migrate();
         Ok(())
     }
 }
@@ -1,9 +1,11 @@
+pub mod extend_config;
 pub mod init_config;
 pub mod quote;
 pub mod send;
 pub mod send_with_lz_token;
 pub mod set_config;
 
+pub use extend_config::*;
 pub use init_config::*;
 pub use quote::*;
 pub use send::*;
@@ -1,7 +1,11 @@
 use crate::*;
+use anchor_lang::Owners;
+use anchor_spl::token_interface::TokenAccount;
 use messagelib_helper::{
-    endpoint::instructions::hash_payload, packet_v1_codec::encode_packet_header,
+    endpoint::instructions::hash_payload, messagelib_interface::Packet,
+    packet_v1_codec::encode_packet_header,
 };
+use treasury_interface::QuoteTreasuryParams;
 use worker_interface::{LzOption, QuoteDvnParams, QuoteExecutorParams};
 
 #[derive(Accounts)]
@@ -25,70 +29,107 @@ pub struct Quote<'info> {
 
 impl Quote<'_> {
     pub fn apply(ctx: &Context<Quote>, params: &QuoteParams) -> Result<MessagingFee> {
-        let (uln_config, executor_config) =
-            get_send_config(&ctx.accounts.send_config, &ctx.accounts.default_send_config)?;
+        Ok(Self::quote_detailed(ctx, params)?.fee)
+    }
+
+    pub fn quote_detailed(
+        ctx: &Context<Quote>,
+        params: &QuoteParams,
+    ) -> Result<MessagingFeeDetail> {
+        let treasury_fees = ctx.accounts.uln.treasury_fees();
+        let send_config = local_custom_config::<SendConfig>(&ctx.accounts.send_config)?;
+        let (treasury_accounts, remaining_accounts) =
+            split_treasury_accounts(send_config.treasury_program(), ctx.remaining_accounts)?;
+        let (uln_config, executor_config, _, remaining_accounts) =
+            get_send_config(&send_config, &ctx.accounts.default_send_config, remaining_accounts)?;
 
         let (executor_options, dvn_options) = decode_options(&params.options)?;
 
+        let (worker_accounts, payment_accounts) =
+            split_worker_accounts(&uln_config, remaining_accounts)?;
+        let (payments, dvn_quote_accounts) =
+            match_worker_payments(worker_accounts, payment_accounts, params.pay_in_lz_token)?;
+        let in_token = payments.iter().map(WorkerPayment::is_fee_token).collect::<Vec<_>>();
+
         // executor fee
-        let executor_fee = quote_executor(
+        let (executor_fee, executor_native_fee) = quote_executor(
             &ctx.accounts.uln.key(),
             &executor_config,
-            params.packet.dst_eid,
-            &params.packet.sender,
-            params.packet.message.len() as u64,
+            &params.packet,
             executor_options,
-            &ctx.remaining_accounts[0..4],
+            &worker_accounts[0..4],
+            in_token[0],
         )?;
 
         // dvn fees
-        let dvn_fees = quote_dvns(
+        let (dvn_fees, dvns_native_fee) = quote_dvns(
             &ctx.accounts.uln.key(),
             &uln_config,
-            params.packet.dst_eid,
-            &params.packet.sender,
-            encode_packet_header(&params.packet),
-            hash_payload(&params.packet.guid, &params.packet.message),
+            &params.packet,
             dvn_options,
-            &ctx.remaining_accounts[4..],
+            &worker_accounts[4..],
+            dvn_quote_accounts,
+            &in_token[1..],
         )?;
 
-        let worker_fee = executor_fee.fee + dvn_fees.iter().map(|f| f.fee).sum::<u64>();
-
-        let (native_fee, lz_token_fee) = if let Some(treasury) = ctx.accounts.uln.treasury.as_ref()
-        {
-            let treasury_fee = quote_treasury(treasury, worker_fee, params.pay_in_lz_token)?;
+        let worker_fees = WorkerFees {
+            executor: executor_fee,
+            dvns: dvn_fees,
+            treasury_base: executor_native_fee + dvns_native_fee,
+        };
+        let worker_fee = worker_fees.native_fee();
 
-            if params.pay_in_lz_token {
-                (worker_fee, treasury_fee)
-            } else {
-                (worker_fee + treasury_fee, 0)
-            }
-        } else {
-            (worker_fee, 0)
+        // the treasury fee, None if there is no fee to pay in either unit
+        let treasury_fee = match ctx.accounts.uln.treasury.as_ref() {
+            Some(treasury) => {
+                let program_fee = quote_treasury_program(
+                    &ctx.accounts.uln.key(),
+                    send_config.treasury_program(),
+                    params.packet.dst_eid,
+                    &params.packet.sender,
+                    worker_fees.treasury_base,
+                    params.pay_in_lz_token,
+                    treasury_accounts,
+                )?;
+                let fee = quote_treasury(
+                    treasury,
+                    treasury_fees,
+                    worker_fees.treasury_base,
+                    params.pay_in_lz_token,
+                    program_fee,
+                )?;
+                treasury_fee_detail(treasury, fee, params.pay_in_lz_token)?
+            },
+            None => None,
         };
+        let fee = messaging_fee(worker_fee, treasury_fee.as_ref());
 
-        Ok(MessagingFee { native_fee, lz_token_fee })
+        Ok(MessagingFeeDetail {
+            fee,
+            executor: worker_fees.executor,
+            dvns: worker_fees.dvns,
+            treasury: treasury_fee,
+        })
     }
 }
 
 pub fn quote_executor(
     uln: &Pubkey,
     executor_config: &ExecutorConfig,
-    dst_eid: u32,
-    sender: &Pubkey,
-    calldata_size: u64,
+    packet: &Packet,
     options: Vec<LzOption>,
     // [executor_program, executor_config, price_feed_program, price_feed_config]
     accounts: &[AccountInfo],
-) -> Result<WorkerFee> {
+    in_token: bool,
+) -> Result<(WorkerFee, u64)> {
+    let calldata_size = packet.message.len() as u64;
     require!(
         calldata_size <= executor_config.max_message_size as u64,
         UlnError::ExceededMaxMessageSize
     );
 
     // assert all accounts are non-signer
-    // executor_config should be writable to receive the fee on send(), so it's not checked
+    // executor_config should be writable if it receives the fee on send(), so it's not checked
     for account in accounts {
         require!(!account.is_signer, UlnError::NonSigner);
     }
@@ -100,8 +141,13 @@ pub fn quote_executor(
     // assert executor is the same as the executor in the executor config
     require!(executor_config.executor == executor_acc.key(), UlnError::InvalidExecutor);
 
-    let params =
-        QuoteExecutorParams { msglib: uln.key(), dst_eid, sender: *sender, calldata_size, options };
+    let params = QuoteExecutorParams {
+        msglib: uln.key(),
+        dst_eid: packet.dst_eid,
+        sender: packet.sender,
+        calldata_size,
+        options,
+    };
     let cpi_ctx = CpiContext::new(
         executor_program.to_account_info(),
         worker_interface::cpi::accounts::Quote {
@@ -110,31 +156,48 @@ pub fn quote_executor(
             price_feed_config: accounts[3].to_account_info(),
         },
     );
-    let fee = worker_interface::cpi::quote_executor(cpi_ctx, params)?.get();
-    Ok(WorkerFee { worker: executor_config.executor, fee })
+    if in_token {
+        let token_fee = worker_interface::cpi::quote_executor_in_token(cpi_ctx, params)?
+            .get()
+            .ok_or(UlnError::FeeTokenUnavailable)?;
+        let fee = WorkerFee {
+            worker: executor_config.executor,
+            fee: token_fee.fee,
+            fee_token: Some(token_fee.mint),
+        };
+        Ok((fee, token_fee.native_fee))
+    } else {
+        let fee = worker_interface::cpi::quote_executor(cpi_ctx, params)?.get();
+        Ok((WorkerFee { worker: executor_config.executor, fee, fee_token: None }, fee))
+    }
 }
 
-pub fn quote_dvns(
+/// Quotes the dvns with their 4 accounts each, returning their fees and the sum of the fees in
+/// lamports. The dvn quote accounts are forwarded to the quote of the dvn program that owns them,
+/// e.g. the oapp pricing or the acl page of the sender.
+pub fn quote_dvns<'info>(
     uln: &Pubkey,
     uln_config: &UlnConfig,
-    dst_eid: u32,
-    sender: &Pubkey,
-    packet_header: Vec<u8>,
-    payload_hash: [u8; 32],
+    packet: &Packet,
     options: DVNOptions,
     // [dvn_program, dvn_config, price_feed_program, price_feed_config, ...]
-    accounts: &[AccountInfo],
-) -> Result<Vec<WorkerFee>> {
+    accounts: &[AccountInfo<'info>],
+    dvn_quote_accounts: &[AccountInfo<'info>],
+    in_token: &[bool],
+) -> Result<(Vec<WorkerFee>, u64)> {
     let length = uln_config.required_dvns.len() + uln_config.optional_dvns.len();
     require!(accounts.len() == length * 4, UlnError::InvalidAccountLength);
 
     // assert all accounts are non-signer
-    // dvn_config should be writable to receive the fee on send(), so it's not checked
+    // dvn_config should be writable if it receives the fee on send(), so it's not checked
     for account in accounts {
         require!(!account.is_signer, UlnError::NonSigner);
     }
 
+    let packet_header = encode_packet_header(packet);
+    let payload_hash = hash_payload(&packet.guid, &packet.message);
     let mut fees = Vec::with_capacity(length);
+    let mut native_fee = 0u64;
     for (i, chunk) in accounts.chunks(4).enumerate() {
         let dvn_program = &chunk[0];
         let dvn_acc = &chunk[1];
@@ -151,8 +214,8 @@ pub fn quote_dvns(
         let options = options.get(&(i as u8)).cloned().unwrap_or_default();
         let params = QuoteDvnParams {
             msglib: uln.key(),
-            dst_eid,
-            sender: *sender,
+            dst_eid: packet.dst_eid,
+            sender: packet.sender,
             packet_header: packet_header.clone(),
             payload_hash,
             confirmations: uln_config.confirmations,
@@ -165,23 +228,528 @@ pub fn quote_dvns(
                 price_feed_program: chunk[2].to_account_info(),
                 price_feed_config: chunk[3].to_account_info(),
             },
+        )
+        .with_remaining_accounts(
+            dvn_quote_accounts
+                .iter()
+                .filter(|account| account.owner == dvn_program.key)
+                .cloned()
+                .collect(),
         );
-        let fee = worker_interface::cpi::quote_dvn(cpi_ctx, params)?.get();
-        fees.push(WorkerFee { worker: dvn, fee });
+        if in_token[i] {
+            let token_fee = worker_interface::cpi::quote_dvn_in_token(cpi_ctx, params)?
+                .get()
+                .ok_or(UlnError::FeeTokenUnavailable)?;
+            fees.push(WorkerFee {
+                worker: dvn,
+                fee: token_fee.fee,
+                fee_token: Some(token_fee.mint),
+            });
+            native_fee += token_fee.native_fee;
+        } else {
+            let fee = worker_interface::cpi::quote_dvn(cpi_ctx, params)?.get();
+            fees.push(WorkerFee { worker: dvn, fee, fee_token: None });
+            native_fee += fee;
+        }
+    }
+    Ok((fees, native_fee))
+}
+
+/// Splits the remaining accounts into the worker accounts, 4 for the executor and 4 for each dvn,
+/// and the trailing payment and dvn quote accounts.
+pub(crate) fn split_worker_accounts<'a, 'info>(
+    uln_config: &UlnConfig,
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    let dvn_count = uln_config.required_dvns.len() + uln_config.optional_dvns.len();
+    let worker_accounts_len = (1 + dvn_count) * 4;
+    require!(accounts.len() >= worker_accounts_len, UlnError::InvalidAccountLength);
+    Ok(accounts.split_at(worker_accounts_len))
+}
+
+pub(crate) enum WorkerPayment<'a, 'info> {
+    /// lamports transferred to the worker config account
+    Native,
+    /// lamports credited to a fee ledger shard of the worker, to be claimed by claim_fees()
+    FeeLedger(&'a AccountInfo<'info>),
+    /// the spl fee token of the worker transferred with
+    /// [token_program, mint, payer_token_account, worker_token_account]
+    FeeToken(&'a [AccountInfo<'info>]),
+}
+
+impl WorkerPayment<'_, '_> {
+    pub fn is_fee_token(&self) -> bool {
+        matches!(self, WorkerPayment::FeeToken(_))
+    }
+}
+
+/// Matches the trailing payment accounts to the workers, in the same order as the workers. Each
+/// worker is paid in lamports to its config account unless the payment accounts provide either
+///   1) a fee ledger shard of the worker, or
+///   2) 4 fee token accounts whose worker token account is owned by the worker config account,
+///      which is only allowed if the sender pays in lz token
+/// The accounts after the payment accounts are the dvn quote accounts, which must be owned by the
+/// dvn programs. They are returned to be forwarded to the dvn quotes.
+pub(crate) fn match_worker_payments<'a, 'info>(
+    worker_accounts: &'a [AccountInfo<'info>],
+    payment_accounts: &'a [AccountInfo<'info>],
+    pay_in_lz_token: bool,
+) -> Result<(Vec<WorkerPayment<'a, 'info>>, &'a [AccountInfo<'info>])> {
+    let mut rest = payment_accounts;
+    let mut payments = Vec::with_capacity(worker_accounts.len() / 4);
+    for worker_chunk in worker_accounts.chunks(4) {
+        let worker = worker_chunk[1].key;
+        let payment = match rest {
+            [fee_ledger, tail @ ..] if is_fee_ledger_of(fee_ledger, worker) => {
+                rest = tail;
+                WorkerPayment::FeeLedger(fee_ledger)
+            },
+            [_, _, _, worker_token_account, tail @ ..]
+                if is_token_account_of(worker_token_account, worker) =>
+            {
+                require!(pay_in_lz_token, UlnError::FeeTokenUnavailable);
+                let fee_token_accounts = &rest[..4];
+                rest = tail;
+                WorkerPayment::FeeToken(fee_token_accounts)
+            },
+            _ => WorkerPayment::Native,
+        };
+        payments.push(payment);
+    }
+    let dvn_programs =
+        worker_accounts.chunks(4).skip(1).map(|chunk| chunk[0].key).collect::<Vec<_>>();
+    require!(
+        rest.iter().all(|account| dvn_programs.contains(&account.owner)),
+        UlnError::InvalidAccountLength
+    );
+    Ok((payments, rest))
+}
+
+fn is_fee_ledger_of(account: &AccountInfo, worker: &Pubkey) -> bool {
+    account.owner == &ID
+        && account
+            .try_borrow_data()
+            .ok()
+            .and_then(|data| FeeLedger::try_deserialize(&mut &data[..]).ok())
+            .map_or(false, |fee_ledger| fee_ledger.worker == *worker)
+}
+
+fn is_token_account_of(account: &AccountInfo, worker: &Pubkey) -> bool {
+    deserialize_token_account::<TokenAccount>(account)
+        .map_or(false, |token_account| token_account.owner == *worker)
+}
+
+pub(crate) fn deserialize_token_account<T: AccountDeserialize + Owners>(
+    account: &AccountInfo,
+) -> Result<T> {
+    require!(T::owners().contains(account.owner), ErrorCode::AccountOwnedByWrongProgram);
+    T::try_deserialize(&mut &account.try_borrow_data()?[..])
+}
+
+/// The fees of the executor and the dvns of a packet
+pub(crate) struct WorkerFees {
+    pub executor: WorkerFee,
+    pub dvns: Vec<WorkerFee>,
+    // the worker fees in lamports, counting the fees paid in a fee token by the lamports they are
+    // converted from, which the treasury fee is charged on
+    pub treasury_base: u64,
+}
+
+impl WorkerFees {
+    /// The worker fees that are paid in lamports
+    pub fn native_fee(&self) -> u64 {
+        std::iter::once(&self.executor)
+            .chain(&self.dvns)
+            .filter(|f| f.fee_token.is_none())
+            .map(|f| f.fee)
+            .sum()
+    }
+}
+
+/// Splits the treasury program accounts off the front of the remaining accounts,
+/// [treasury_program, treasury_config] if the send config of the oapp has the treasury program and
+/// none otherwise.
+pub(crate) fn split_treasury_accounts<'a, 'info>(
+    treasury_program: Option<&TreasuryProgram>,
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    let treasury_accounts_len = if treasury_program.is_some() { 2 } else { 0 };
+    require!(accounts.len() >= treasury_accounts_len, UlnError::InvalidAccountLength);
+    Ok(accounts.split_at(treasury_accounts_len))
+}
+
+/// Returns the fee quoted by the treasury program, or None if the treasury program is not set
+pub(crate) fn quote_treasury_program(
+    uln: &Pubkey,
+    treasury_program: Option<&TreasuryProgram>,
+    dst_eid: u32,
+    sender: &Pubkey,
+    worker_fee: u64,
+    pay_in_lz_token: bool,
+    // [treasury_program, treasury_config]
+    accounts: &[AccountInfo],
+) -> Result<Option<u64>> {
+    let treasury_program = match treasury_program {
+        Some(treasury_program) => treasury_program,
+        None => return Ok(None),
+    };
+
+    // assert all accounts are non-signer
+    for account in accounts {
+        require!(!account.is_signer, UlnError::NonSigner);
     }
-    Ok(fees)
+
+    let program_acc = &accounts[0];
+    let config_acc = &accounts[1];
+    // assert the treasury program and its config are the ones in the treasury settings
+    require!(
+        program_acc.key() == treasury_program.program
+            && config_acc.key() == treasury_program.config
+            && *config_acc.owner == treasury_program.program,
+        UlnError::InvalidTreasuryProgram
+    );
+
+    let params = QuoteTreasuryParams {
+        msglib: uln.key(),
+        dst_eid,
+        sender: *sender,
+        worker_fee,
+        pay_in_lz_token,
+    };
+    let cpi_ctx = CpiContext::new(
+        program_acc.to_account_info(),
+        treasury_interface::cpi::accounts::QuoteTreasury {
+            treasury_config: config_acc.to_account_info(),
+        },
+    );
+    let fee = treasury_interface::cpi::quote_treasury(cpi_ctx, params)?.get();
+    Ok(Some(fee))
 }
 
+/// Returns the treasury fee paid to the receiver of the unit it is paid in, None if the fee is 0
+pub(crate) fn treasury_fee_detail(
+    treasury: &Treasury,
+    fee: u64,
+    pay_in_lz_token: bool,
+) -> Result<Option<TreasuryFee>> {
+    let receiver = if pay_in_lz_token {
+        treasury.lz_token.as_ref().ok_or(UlnError::LzTokenUnavailable)?.receiver
+    } else {
+        treasury.native_receiver
+    };
+    Ok(Some(TreasuryFee { treasury: receiver, fee, pay_in_lz_token }).filter(|_| fee > 0))
+}
+
+/// Returns the messaging fee of the worker fee paid in lamports and the treasury fee
+pub(crate) fn messaging_fee(worker_fee: u64, treasury_fee: Option<&TreasuryFee>) -> MessagingFee {
+    match treasury_fee {
+        Some(treasury_fee) if treasury_fee.pay_in_lz_token => {
+            MessagingFee { native_fee: worker_fee, lz_token_fee: treasury_fee.fee }
+        },
+        Some(treasury_fee) => {
+            MessagingFee { native_fee: worker_fee + treasury_fee.fee, lz_token_fee: 0 }
+        },
+        None => MessagingFee { native_fee: worker_fee, lz_token_fee: 0 },
+    }
+}
+
+/// Returns the treasury fee with the fee bounds applied. The fee is calculated from the static fee
+/// settings unless it is quoted by the treasury program.
 pub(crate) fn quote_treasury(
     treasury: &Treasury,
+    fees: &TreasuryFees,
     worker_fee: u64,
     pay_in_lz_token: bool,
+    program_fee: Option<u64>,
 ) -> Result<u64> {
     if pay_in_lz_token {
         let treasury = treasury.lz_token.as_ref().ok_or(UlnError::LzTokenUnavailable)?;
-        Ok(treasury.fee)
+        let fee = if let Some(fee) = program_fee {
+            fee
+        } else if fees.lz_token_fee_bps > 0 {
+            // the bps fee in native converted to lz token
+            let native_fee = bps_of(worker_fee, fees.lz_token_fee_bps)?;
+            (native_fee as u128)
+                .checked_mul(fees.lz_token_price_ratio)
+                .map(|fee| fee / LZ_TOKEN_PRICE_RATIO_DENOMINATOR)
+                .and_then(|fee| u64::try_from(fee).ok())
+                .ok_or(UlnError::TreasuryFeeOverflow)?
+        } else {
+            treasury.fee
+        };
+        Ok(fees.lz_token_fee_bounds.apply(fee))
     } else {
         // pay in native
-        Ok(worker_fee * treasury.native_fee_bps / BPS_DENOMINATOR)
+        let fee = match program_fee {
+            Some(fee) => fee,
+            None => bps_of(worker_fee, treasury.native_fee_bps)?,
+        };
+        Ok(fees.native_fee_bounds.apply(fee))
+    }
+}
+
+fn bps_of(amount: u64, bps: u64) -> Result<u64> {
+    (amount as u128)
+        .checked_mul(bps as u128)
+        .map(|fee| fee / BPS_DENOMINATOR as u128)
+        .and_then(|fee| u64::try_from(fee).ok())
+        .ok_or(UlnError::TreasuryFeeOverflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn treasury(native_fee_bps: u64, lz_token: Option<LzTokenTreasury>) -> Treasury {
+        Treasury { admin: None, native_receiver: Pubkey::new_unique(), native_fee_bps, lz_token }
+    }
+
+    fn lz_token_treasury(fee: u64) -> LzTokenTreasury {
+        LzTokenTreasury { receiver: Pubkey::new_unique(), fee }
+    }
+
+    fn lz_token_fees(lz_token_fee_bps: u64, lz_token_price_ratio: u128) -> TreasuryFees {
+        TreasuryFees { lz_token_fee_bps, lz_token_price_ratio, ..Default::default() }
+    }
+
+    #[test]
+    fn test_quote_treasury_native() {
+        let mut treasury = treasury(100, None);
+        let mut fees = TreasuryFees::default();
+        assert_eq!(quote_treasury(&treasury, &fees, 10000, false, None).unwrap(), 100);
+
+        fees.native_fee_bounds = TreasuryFeeBounds { min_fee: 200, max_fee: Some(300) };
+        assert_eq!(quote_treasury(&treasury, &fees, 10000, false, None).unwrap(), 200);
+        assert_eq!(quote_treasury(&treasury, &fees, 25000, false, None).unwrap(), 250);
+        assert_eq!(quote_treasury(&treasury, &fees, 50000, false, None).unwrap(), 300);
+
+        // no overflow for the max worker fee
+        treasury.native_fee_bps = BPS_DENOMINATOR;
+        fees.native_fee_bounds = TreasuryFeeBounds::default();
+        assert_eq!(quote_treasury(&treasury, &fees, u64::MAX, false, None).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_quote_treasury_on_token_paid_workers() {
+        let worker_fee =
+            |fee, fee_token| WorkerFee { worker: Pubkey::new_unique(), fee, fee_token };
+        // the dvn is paid 5000 token units converted from 200 lamports
+        let worker_fees = WorkerFees {
+            executor: worker_fee(100, None),
+            dvns: vec![worker_fee(5000, Some(Pubkey::new_unique())), worker_fee(300, None)],
+            treasury_base: 600,
+        };
+        assert_eq!(worker_fees.native_fee(), 400);
+
+        // the treasury fee is charged on the fee of the token paid dvn too
+        let treasury = treasury(1000, None);
+        let fees = TreasuryFees::default();
+        let fee = quote_treasury(&treasury, &fees, worker_fees.treasury_base, false, None);
+        assert_eq!(fee.unwrap(), 60);
+    }
+
+    #[test]
+    fn test_quote_treasury_lz_token_flat() {
+        let fees = TreasuryFees::default();
+        let treasury = treasury(100, Some(lz_token_treasury(1000)));
+        assert_eq!(quote_treasury(&treasury, &fees, 10000, true, None).unwrap(), 1000);
+        assert!(quote_treasury(&self::treasury(100, None), &fees, 10000, true, None)
+            .unwrap_err()
+            .to_string()
+            .contains("LzTokenUnavailable"));
+    }
+
+    #[test]
+    fn test_quote_treasury_lz_token_bps() {
+        // 1 lamport = 2 lz token units
+        let price_ratio = 2 * LZ_TOKEN_PRICE_RATIO_DENOMINATOR;
+        let treasury = treasury(0, Some(lz_token_treasury(1000)));
+        let mut fees = lz_token_fees(100, price_ratio);
+        assert_eq!(quote_treasury(&treasury, &fees, 10000, true, None).unwrap(), 200);
+
+        fees.lz_token_fee_bounds = TreasuryFeeBounds { min_fee: 500, max_fee: None };
+        assert_eq!(quote_treasury(&treasury, &fees, 10000, true, None).unwrap(), 500);
+
+        fees.lz_token_fee_bounds = TreasuryFeeBounds { min_fee: 0, max_fee: Some(150) };
+        assert_eq!(quote_treasury(&treasury, &fees, 10000, true, None).unwrap(), 150);
+
+        fees.lz_token_price_ratio = u128::MAX;
+        assert!(quote_treasury(&treasury, &fees, 10000, true, None)
+            .unwrap_err()
+            .to_string()
+            .contains("TreasuryFeeOverflow"));
+    }
+
+    #[test]
+    fn test_quote_treasury_program_fee() {
+        let price_ratio = 2 * LZ_TOKEN_PRICE_RATIO_DENOMINATOR;
+        let mut treasury = treasury(100, Some(lz_token_treasury(1000)));
+        let mut fees = lz_token_fees(100, price_ratio);
+        // the program fee overrides the static fee settings
+        assert_eq!(quote_treasury(&treasury, &fees, 10000, false, Some(7)).unwrap(), 7);
+        assert_eq!(quote_treasury(&treasury, &fees, 10000, true, Some(7)).unwrap(), 7);
+
+        // but not the fee bounds
+        fees.native_fee_bounds = TreasuryFeeBounds { min_fee: 10, max_fee: Some(20) };
+        assert_eq!(quote_treasury(&treasury, &fees, 10000, false, Some(7)).unwrap(), 10);
+        assert_eq!(quote_treasury(&treasury, &fees, 10000, false, Some(u64::MAX)).unwrap(), 20);
+
+        // the lz token treasury is still required to pay in lz token
+        treasury.lz_token = None;
+        assert!(quote_treasury(&treasury, &fees, 10000, true, Some(7)).is_err());
+    }
+
+    #[test]
+    fn test_treasury_fee_detail() {
+        let treasury = treasury(100, Some(lz_token_treasury(1000)));
+        let lz_token_receiver = treasury.lz_token.as_ref().unwrap().receiver;
+
+        let fee = treasury_fee_detail(&treasury, 10, false).unwrap().unwrap();
+        assert_eq!(fee.treasury, treasury.native_receiver);
+        assert_eq!((fee.fee, fee.pay_in_lz_token), (10, false));
+        let fee = treasury_fee_detail(&treasury, 10, true).unwrap().unwrap();
+        assert_eq!(fee.treasury, lz_token_receiver);
+        assert_eq!((fee.fee, fee.pay_in_lz_token), (10, true));
+
+        // no treasury fee to pay in either unit
+        assert!(treasury_fee_detail(&treasury, 0, false).unwrap().is_none());
+        assert!(treasury_fee_detail(&treasury, 0, true).unwrap().is_none());
+
+        // the lz token treasury is required to pay in lz token
+        assert!(treasury_fee_detail(&self::treasury(100, None), 0, true).is_err());
+    }
+
+    #[test]
+    fn test_messaging_fee() {
+        let treasury_fee = |fee: u64, pay_in_lz_token: bool| TreasuryFee {
+            treasury: Pubkey::new_unique(),
+            fee,
+            pay_in_lz_token,
+        };
+
+        let fee = messaging_fee(100, Some(&treasury_fee(10, false)));
+        assert_eq!((fee.native_fee, fee.lz_token_fee), (110, 0));
+        let fee = messaging_fee(100, Some(&treasury_fee(10, true)));
+        assert_eq!((fee.native_fee, fee.lz_token_fee), (100, 10));
+        let fee = messaging_fee(100, None);
+        assert_eq!((fee.native_fee, fee.lz_token_fee), (100, 0));
+    }
+
+    #[test]
+    fn test_split_treasury_accounts() {
+        let keys = (0..3).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let mut lamports = [0; 3];
+        let mut data = [[0u8; 0]; 3];
+        let accounts = keys
+            .iter()
+            .zip(lamports.iter_mut().zip(data.iter_mut()))
+            .map(|(key, (lamports, data))| {
+                AccountInfo::new(key, false, false, lamports, data, key, false, 0)
+            })
+            .collect::<Vec<_>>();
+
+        // the oapps without the treasury program pass no treasury accounts
+        let (treasury_accounts, rest) = split_treasury_accounts(None, &accounts).unwrap();
+        assert!(treasury_accounts.is_empty());
+        assert_eq!(rest.len(), 3);
+
+        let program = TreasuryProgram { program: keys[0], config: keys[1] };
+        let (treasury_accounts, rest) = split_treasury_accounts(Some(&program), &accounts).unwrap();
+        assert_eq!(treasury_accounts.len(), 2);
+        assert_eq!(rest[0].key, &keys[2]);
+        assert!(split_treasury_accounts(Some(&program), &accounts[..1]).is_err());
+    }
+
+    #[test]
+    fn test_treasury_fee_bounds_sanity_check() {
+        assert!(TreasuryFeeBounds { min_fee: 1, max_fee: Some(1) }.sanity_check().is_ok());
+        assert!(TreasuryFeeBounds { min_fee: 2, max_fee: None }.sanity_check().is_ok());
+        assert!(TreasuryFeeBounds { min_fee: 2, max_fee: Some(1) }.sanity_check().is_err());
+    }
+
+    #[test]
+    fn test_match_worker_payments_with_fee_ledgers() {
+        use crate::state::fee_ledger::tests::fee_ledger_data;
+
+        let keys = (0..8).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let (ledger_key, other_ledger_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut lamports = [0; 10];
+        let mut data = vec![vec![]; 8];
+        let (first_lamports, rest_lamports) = lamports.split_at_mut(8);
+        let worker_accounts = keys
+            .iter()
+            .zip(first_lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &ID, false, 0)
+            })
+            .collect::<Vec<_>>();
+        // the fee ledger of the second worker, whose config account is at index 5
+        let mut ledger_data = fee_ledger_data(keys[5], vec![]);
+        let mut other_ledger_data = fee_ledger_data(Pubkey::new_unique(), vec![]);
+        let (ledger_lamports, other_lamports) = rest_lamports.split_at_mut(1);
+        let fee_ledger = AccountInfo::new(
+            &ledger_key,
+            false,
+            true,
+            &mut ledger_lamports[0],
+            &mut ledger_data,
+            &ID,
+            false,
+            0,
+        );
+        let other_ledger = AccountInfo::new(
+            &other_ledger_key,
+            false,
+            true,
+            &mut other_lamports[0],
+            &mut other_ledger_data,
+            &ID,
+            false,
+            0,
+        );
+
+        let payment_accounts = [fee_ledger.clone()];
+        let (payments, dvn_quote_accounts) =
+            match_worker_payments(&worker_accounts, &payment_accounts, false).unwrap();
+        assert!(dvn_quote_accounts.is_empty());
+        assert!(matches!(payments[0], WorkerPayment::Native));
+        assert!(
+            matches!(payments[1], WorkerPayment::FeeLedger(ledger) if ledger.key == &ledger_key)
+        );
+
+        // the fee ledger of another worker is not matched
+        let payment_accounts = [other_ledger];
+        let result = match_worker_payments(&worker_accounts, &payment_accounts, false);
+        assert_eq!(result.err().unwrap(), UlnError::InvalidAccountLength.into());
+    }
+
+    #[test]
+    fn test_match_worker_payments_with_dvn_quote_accounts() {
+        let keys = (0..10).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let mut lamports = [0; 10];
+        let mut data = vec![vec![]; 10];
+        // the dvn program is at index 4, the first account of the second worker
+        let owners = [ID, ID, ID, ID, ID, ID, ID, ID, keys[4], keys[0]];
+        let accounts = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .zip(owners.iter())
+            .map(|(((key, lamports), data), owner)| {
+                AccountInfo::new(key, false, false, lamports, data, owner, false, 0)
+            })
+            .collect::<Vec<_>>();
+        let (worker_accounts, rest) = accounts.split_at(8);
+
+        let (payments, dvn_quote_accounts) =
+            match_worker_payments(worker_accounts, &rest[..1], false).unwrap();
+        assert!(payments.iter().all(|payment| matches!(payment, WorkerPayment::Native)));
+        assert_eq!(dvn_quote_accounts.len(), 1);
+        assert_eq!(dvn_quote_accounts[0].key, &keys[8]);
+
+        // the trailing accounts must be owned by the dvn programs, not by the executor program
+        let result = match_worker_payments(worker_accounts, rest, false);
+        assert_eq!(result.err().unwrap(), UlnError::InvalidAccountLength.into());
     }
 }
@@ -1,10 +1,8 @@
 use crate::*;
 use anchor_lang::solana_program::{program, system_instruction};
-use messagelib_helper::{
-    endpoint::instructions::hash_payload,
-    messagelib_interface::Packet,
-    packet_v1_codec::{encode, encode_packet_header},
-};
+use anchor_lang::Ids;
+use anchor_spl::token_interface::{self, Mint, TokenInterface, TransferChecked};
+use messagelib_helper::{messagelib_interface::Packet, packet_v1_codec::encode};
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -42,20 +40,40 @@ impl Send<'_> {
         ctx: &mut Context<'_, '_, 'c, 'info, Send<'info>>,
         params: &SendParams,
     ) -> Result<(MessagingFee, Vec<u8>)> {
-        let (executor_fee, dvn_fees) = assign_job_to_workers(
-            &ctx.accounts.uln.key(),
-            &ctx.accounts.payer,
-            &params.packet,
-            &params.options,
-            &ctx.accounts.send_config,
-            &ctx.accounts.default_send_config,
-            ctx.remaining_accounts,
-        )?;
-        let worker_fee = executor_fee.fee + dvn_fees.iter().map(|f| f.fee).sum::<u64>();
+        let treasury_fees = ctx.accounts.uln.treasury_fees().clone();
+        let send_config = local_custom_config::<SendConfig>(&ctx.accounts.send_config)?;
+        let (treasury_accounts, remaining_accounts) =
+            split_treasury_accounts(send_config.treasury_program(), ctx.remaining_accounts)?;
+        let job = WorkerJob {
+            uln: &ctx.accounts.uln.key(),
+            payer: &ctx.accounts.payer,
+            packet: &params.packet,
+            options: &params.options,
+            send_config: &send_config,
+            default_send_config: &ctx.accounts.default_send_config,
+            pay_in_lz_token: false,
+        };
+        let worker_fees = assign_job_to_workers(&job, remaining_accounts)?;
+        let worker_fee = worker_fees.native_fee();
 
         // treasury fee
         let treasury_fee = if let Some(treasury) = &ctx.accounts.uln.treasury {
-            let fee = quote_treasury(treasury, worker_fee, false)?;
+            let program_fee = quote_treasury_program(
+                &ctx.accounts.uln.key(),
+                send_config.treasury_program(),
+                params.packet.dst_eid,
+                &params.packet.sender,
+                worker_fees.treasury_base,
+                false,
+                treasury_accounts,
+            )?;
+            let fee = quote_treasury(
+                treasury,
+                &treasury_fees,
+                worker_fees.treasury_base,
+                false,
+                program_fee,
+            )?;
 
             // assert the treasury receiver is the same as the treasury account
             let treasury_acc = ctx.accounts.treasury.as_ref().ok_or(UlnError::InvalidTreasury)?;
@@ -81,80 +99,148 @@ impl Send<'_> {
         let total_fee = worker_fee + treasury_fee.as_ref().map(|f| f.fee).unwrap_or(0);
         require!(params.native_fee >= total_fee, UlnError::InsufficientFee);
 
-        emit_cpi!(FeesPaidEvent { executor: executor_fee, dvns: dvn_fees, treasury: treasury_fee });
+        emit_cpi!(FeesPaidEvent {
+            executor: worker_fees.executor,
+            dvns: worker_fees.dvns,
+            treasury: treasury_fee
+        });
 
         Ok((MessagingFee { native_fee: total_fee, lz_token_fee: 0 }, encode(&params.packet)))
     }
 }
 
+/// The packet to be sent by the workers, paid by the payer
+pub(crate) struct WorkerJob<'a, 'info> {
+    pub uln: &'a Pubkey,
+    pub payer: &'a AccountInfo<'info>,
+    pub packet: &'a Packet,
+    pub options: &'a [u8],
+    pub send_config: &'a SendConfig,
+    pub default_send_config: &'a SendConfig,
+    pub pay_in_lz_token: bool,
+}
+
 pub(crate) fn assign_job_to_workers<'c: 'info, 'info>(
-    uln: &Pubkey,
-    payer: &AccountInfo<'info>,
-    packet: &Packet,
-    options: &[u8],
-    send_config: &AccountInfo,
-    default_send_config: &SendConfig,
-    worker_accounts: &[AccountInfo<'info>],
-) -> Result<(WorkerFee, Vec<WorkerFee>)> {
-    let (uln_config, executor_config) = get_send_config(send_config, default_send_config)?;
+    job: &WorkerJob<'_, 'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<WorkerFees> {
+    let WorkerJob { uln, payer, packet, options, .. } = *job;
+    let (uln_config, executor_config, max_fees, remaining_accounts) =
+        get_send_config(job.send_config, job.default_send_config, remaining_accounts)?;
     let (executor_options, dvn_options) = decode_options(options)?;
 
+    let (worker_accounts, payment_accounts) =
+        split_worker_accounts(&uln_config, remaining_accounts)?;
+    let (payments, dvn_quote_accounts) =
+        match_worker_payments(worker_accounts, payment_accounts, job.pay_in_lz_token)?;
+
     // pay executor fee
     let executor_accounts = &worker_accounts[0..4]; // each worker can have 4 accounts
-    let executor_fee = quote_executor(
+    let (executor_fee, executor_native_fee) = quote_executor(
         uln,
         &executor_config,
-        packet.dst_eid,
-        &packet.sender,
-        packet.message.len() as u64,
+        packet,
         executor_options,
         executor_accounts,
+        payments[0].is_fee_token(),
     )?;
-    if executor_fee.fee > 0 {
-        // the account at index 1 is the executor config account, which is the account that needs to be paid
-        program::invoke(
-            &system_instruction::transfer(payer.key, executor_accounts[1].key, executor_fee.fee),
-            &[payer.to_account_info(), executor_accounts[1].to_account_info()],
-        )?;
-    }
+    max_fees.check(&executor_fee)?;
+    // the account at index 1 is the executor config account, which is the account that needs to be paid
+    pay_worker(payer, &executor_fee, packet.dst_eid, &executor_accounts[1], &payments[0])?;
 
     // pay dvn fees
     let dvn_accounts = &worker_accounts[4..];
-    let dvn_fees = quote_dvns(
+    let (dvn_fees, dvns_native_fee) = quote_dvns(
         uln,
         &uln_config,
-        packet.dst_eid,
-        &packet.sender,
-        encode_packet_header(&packet),
-        hash_payload(&packet.guid, &packet.message),
+        packet,
         dvn_options,
         dvn_accounts,
+        dvn_quote_accounts,
+        &payments[1..].iter().map(WorkerPayment::is_fee_token).collect::<Vec<_>>(),
     )?;
     for (i, chunk) in dvn_accounts.chunks(4).enumerate() {
-        let fee = dvn_fees[i].fee;
-        if fee > 0 {
-            // the account at index 1 is the dvn config account,
-            // which is the account that needs to be paid
-            let dvn_acc = &chunk[1];
-            program::invoke(
-                &system_instruction::transfer(payer.key, dvn_acc.key, fee),
-                &[payer.to_account_info(), chunk[1].to_account_info()],
-            )?;
-        }
+        max_fees.check(&dvn_fees[i])?;
+        // the account at index 1 is the dvn config account,
+        // which is the account that needs to be paid
+        pay_worker(payer, &dvn_fees[i], packet.dst_eid, &chunk[1], &payments[i + 1])?;
     }
 
-    Ok((executor_fee, dvn_fees))
+    Ok(WorkerFees {
+        executor: executor_fee,
+        dvns: dvn_fees,
+        treasury_base: executor_native_fee + dvns_native_fee,
+    })
 }
 
-pub(crate) fn get_send_config(
-    custom_config_acc: &AccountInfo,
+fn pay_worker<'info>(
+    payer: &AccountInfo<'info>,
+    worker_fee: &WorkerFee,
+    dst_eid: u32,
+    worker_acc: &AccountInfo<'info>,
+    payment: &WorkerPayment<'_, 'info>,
+) -> Result<()> {
+    if worker_fee.fee == 0 {
+        return Ok(());
+    }
+
+    match payment {
+        WorkerPayment::Native => transfer_lamports(payer, worker_acc, worker_fee.fee),
+        WorkerPayment::FeeLedger(fee_ledger) => {
+            transfer_lamports(payer, fee_ledger, worker_fee.fee)?;
+            credit_fee_ledger(fee_ledger, dst_eid, worker_fee.fee)
+        },
+        WorkerPayment::FeeToken(accounts) => {
+            // the worker token account is asserted to be owned by the worker in match_worker_payments()
+            let token_program = &accounts[0];
+            require!(
+                TokenInterface::ids().contains(token_program.key),
+                ErrorCode::InvalidProgramId
+            );
+            let mint = deserialize_token_account::<Mint>(&accounts[1])?;
+            require!(
+                Some(accounts[1].key()) == worker_fee.fee_token,
+                UlnError::InvalidFeeTokenMint
+            );
+            let cpi_accounts = TransferChecked {
+                from: accounts[2].to_account_info(),
+                mint: accounts[1].to_account_info(),
+                to: accounts[3].to_account_info(),
+                authority: payer.to_account_info(),
+            };
+            let cpi_context = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            token_interface::transfer_checked(cpi_context, worker_fee.fee, mint.decimals)
+        },
+    }
+}
+
+fn transfer_lamports<'info>(
+    payer: &AccountInfo<'info>,
+    receiver: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    program::invoke(
+        &system_instruction::transfer(payer.key, receiver.key, amount),
+        &[payer.to_account_info(), receiver.to_account_info()],
+    )
+    .map_err(Into::into)
+}
+
+/// Returns the send config with the dvn set of the custom config applied, and the remaining
+/// accounts after the dvn set account
+pub(crate) fn get_send_config<'a, 'info>(
+    custom_config: &SendConfig,
     default_config: &SendConfig,
-) -> Result<(UlnConfig, ExecutorConfig)> {
-    let custom_config = local_custom_config::<SendConfig>(custom_config_acc)?;
-    let uln_config = UlnConfig::get_config(&default_config.uln, &custom_config.uln)?;
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(UlnConfig, ExecutorConfig, WorkerMaxFees, &'a [AccountInfo<'info>])> {
+    let (dvn_set, accounts) = split_dvn_set_account(custom_config.dvn_set(), accounts)?;
+    let mut uln_config = UlnConfig::get_config(&default_config.uln, &custom_config.uln)?;
+    if let Some(dvn_set) = dvn_set {
+        uln_config.apply_dvn_set(&dvn_set)?;
+    }
     let executor_config =
         ExecutorConfig::get_config(&default_config.executor, &custom_config.executor);
-    Ok((uln_config, executor_config))
+    Ok((uln_config, executor_config, custom_config.max_fees().clone(), accounts))
 }
 
 pub(crate) fn local_custom_config<T: Default + AccountDeserialize>(
@@ -169,3 +255,200 @@ pub(crate) fn local_custom_config<T: Default + AccountDeserialize>(
     };
     Ok(custom_config)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::{
+        entrypoint::ProgramResult, instruction::Instruction, program_pack::Pack, program_stubs,
+    };
+    use anchor_spl::token::spl_token;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static INVOKED: RefCell<Vec<Instruction>> = RefCell::new(Vec::new());
+    }
+
+    /// The instructions invoked by the test on the current thread
+    fn take_invoked() -> Vec<Instruction> {
+        INVOKED.with(|invoked| invoked.take())
+    }
+
+    struct RecordingStubs;
+
+    impl program_stubs::SyscallStubs for RecordingStubs {
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            _account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            INVOKED.with(|invoked| invoked.borrow_mut().push(instruction.clone()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_pay_worker_in_fee_token() {
+        program_stubs::set_syscall_stubs(Box::new(RecordingStubs));
+
+        let (payer_key, worker_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mint_key, payer_token_key, worker_token_key) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let token_program_id = spl_token::ID;
+        let system_program_id = anchor_lang::system_program::ID;
+        let mut mint_data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint::pack(
+            spl_token::state::Mint { decimals: 6, is_initialized: true, ..Default::default() },
+            &mut mint_data,
+        )
+        .unwrap();
+        let (mut l0, mut l1, mut l2, mut l3, mut l4, mut l5) = (0, 0, 0, 0, 0, 0);
+        let (mut d0, mut d1, mut d2, mut d3, mut d4) = (vec![], vec![], vec![], vec![], vec![]);
+        let payer = AccountInfo::new(
+            &payer_key,
+            true,
+            true,
+            &mut l0,
+            &mut d0,
+            &system_program_id,
+            false,
+            0,
+        );
+        let worker = AccountInfo::new(&worker_key, false, true, &mut l1, &mut d1, &ID, false, 0);
+        let token_program = AccountInfo::new(
+            &token_program_id,
+            false,
+            false,
+            &mut l2,
+            &mut d2,
+            &system_program_id,
+            true,
+            0,
+        );
+        let mint = AccountInfo::new(
+            &mint_key,
+            false,
+            false,
+            &mut l3,
+            &mut mint_data,
+            &token_program_id,
+            false,
+            0,
+        );
+        let payer_token = AccountInfo::new(
+            &payer_token_key,
+            false,
+            true,
+            &mut l4,
+            &mut d3,
+            &token_program_id,
+            false,
+            0,
+        );
+        let worker_token = AccountInfo::new(
+            &worker_token_key,
+            false,
+            true,
+            &mut l5,
+            &mut d4,
+            &token_program_id,
+            false,
+            0,
+        );
+        let accounts = [token_program, mint, payer_token, worker_token];
+        let payment = WorkerPayment::FeeToken(&accounts);
+        let fee = WorkerFee { worker: worker_key, fee: 100, fee_token: Some(mint_key) };
+
+        take_invoked();
+        pay_worker(&payer, &fee, 1, &worker, &payment).unwrap();
+        let invoked = take_invoked();
+        assert_eq!(invoked.len(), 1);
+        assert_eq!(invoked[0].program_id, token_program_id);
+        assert_eq!(
+            invoked[0].accounts.iter().map(|a| a.pubkey).collect::<Vec<_>>(),
+            vec![payer_token_key, mint_key, worker_token_key, payer_key]
+        );
+        assert_eq!(
+            spl_token::instruction::TokenInstruction::unpack(&invoked[0].data).unwrap(),
+            spl_token::instruction::TokenInstruction::TransferChecked { amount: 100, decimals: 6 }
+        );
+
+        // the fee token of the worker fee must be the mint
+        let other_fee = WorkerFee { fee_token: Some(Pubkey::new_unique()), ..fee.clone() };
+        let result = pay_worker(&payer, &other_fee, 1, &worker, &payment);
+        assert_eq!(result.unwrap_err(), UlnError::InvalidFeeTokenMint.into());
+
+        // so must the token program
+        let (mut l6, mut d5) = (0, vec![]);
+        let other_program_id = Pubkey::new_unique();
+        let other_program = AccountInfo::new(
+            &other_program_id,
+            false,
+            false,
+            &mut l6,
+            &mut d5,
+            &system_program_id,
+            true,
+            0,
+        );
+        let accounts =
+            [other_program, accounts[1].clone(), accounts[2].clone(), accounts[3].clone()];
+        let result = pay_worker(&payer, &fee, 1, &worker, &WorkerPayment::FeeToken(&accounts));
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidProgramId.into());
+
+        // nothing is paid for no fee
+        let zero_fee = WorkerFee { fee: 0, ..fee };
+        take_invoked();
+        pay_worker(&payer, &zero_fee, 1, &worker, &payment).unwrap();
+        assert!(take_invoked().is_empty());
+    }
+
+    #[test]
+    fn test_pay_worker_to_fee_ledger() {
+        program_stubs::set_syscall_stubs(Box::new(RecordingStubs));
+
+        let (payer_key, worker_key, fee_ledger_key) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let system_program_id = anchor_lang::system_program::ID;
+        let (mut l0, mut l1, mut l2) = (0, 0, 0);
+        let (mut d0, mut d1) = (vec![], vec![]);
+        let mut fee_ledger_data =
+            crate::state::fee_ledger::tests::fee_ledger_data(worker_key, vec![]);
+        let payer = AccountInfo::new(
+            &payer_key,
+            true,
+            true,
+            &mut l0,
+            &mut d0,
+            &system_program_id,
+            false,
+            0,
+        );
+        let worker = AccountInfo::new(&worker_key, false, true, &mut l1, &mut d1, &ID, false, 0);
+        let fee_ledger = AccountInfo::new(
+            &fee_ledger_key,
+            false,
+            true,
+            &mut l2,
+            &mut fee_ledger_data,
+            &ID,
+            false,
+            0,
+        );
+        let fee = WorkerFee { worker: worker_key, fee: 100, fee_token: None };
+
+        take_invoked();
+        pay_worker(&payer, &fee, 2, &worker, &WorkerPayment::FeeLedger(&fee_ledger)).unwrap();
+        pay_worker(&payer, &fee, 2, &worker, &WorkerPayment::FeeLedger(&fee_ledger)).unwrap();
+
+        // the lamports are transferred to the fee ledger instead of the worker
+        let invoked = take_invoked();
+        assert_eq!(invoked.len(), 2);
+        assert_eq!(invoked[0], system_instruction::transfer(&payer_key, &fee_ledger_key, 100));
+        // and credited to the destination
+        let fee_ledger =
+            FeeLedger::try_deserialize(&mut &fee_ledger.try_borrow_data().unwrap()[..]).unwrap();
+        assert_eq!(fee_ledger.fees.fees, vec![EidFee { eid: 2, amount: 200 }]);
+    }
+}
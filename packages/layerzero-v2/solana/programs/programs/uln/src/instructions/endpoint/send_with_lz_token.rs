@@ -56,21 +56,36 @@ impl SendWithLzToken<'_> {
         ctx: &mut Context<'_, '_, 'c, 'info, SendWithLzToken<'info>>,
         params: &SendWithLzTokenParams,
     ) -> Result<(MessagingFee, Vec<u8>)> {
-        let (executor_fee, dvn_fees) = assign_job_to_workers(
-            &ctx.accounts.uln.key(),
-            &ctx.accounts.payer,
-            &params.packet,
-            &params.options,
-            &ctx.accounts.send_config,
-            &ctx.accounts.default_send_config,
-            ctx.remaining_accounts,
-        )?;
-        let worker_fee = executor_fee.fee + dvn_fees.iter().map(|f| f.fee).sum::<u64>();
+        let treasury_fees = ctx.accounts.uln.treasury_fees().clone();
+        let send_config = local_custom_config::<SendConfig>(&ctx.accounts.send_config)?;
+        let (treasury_accounts, remaining_accounts) =
+            split_treasury_accounts(send_config.treasury_program(), ctx.remaining_accounts)?;
+        let job = WorkerJob {
+            uln: &ctx.accounts.uln.key(),
+            payer: &ctx.accounts.payer,
+            packet: &params.packet,
+            options: &params.options,
+            send_config: &send_config,
+            default_send_config: &ctx.accounts.default_send_config,
+            pay_in_lz_token: true,
+        };
+        let worker_fees = assign_job_to_workers(&job, remaining_accounts)?;
+        let worker_fee = worker_fees.native_fee();
         require!(params.native_fee >= worker_fee, UlnError::InsufficientFee);
 
         // the treasury configuration should be available
         let treasury = ctx.accounts.uln.treasury.as_ref().ok_or(UlnError::LzTokenUnavailable)?;
-        let treasury_fee = quote_treasury(treasury, worker_fee, true)?;
+        let program_fee = quote_treasury_program(
+            &ctx.accounts.uln.key(),
+            send_config.treasury_program(),
+            params.packet.dst_eid,
+            &params.packet.sender,
+            worker_fees.treasury_base,
+            true,
+            treasury_accounts,
+        )?;
+        let treasury_fee =
+            quote_treasury(treasury, &treasury_fees, worker_fees.treasury_base, true, program_fee)?;
         require!(params.lz_token_fee >= treasury_fee, UlnError::InsufficientFee);
 
         // assert the treasury receiver
@@ -95,8 +110,8 @@ impl SendWithLzToken<'_> {
         }
 
         emit_cpi!(FeesPaidEvent {
-            executor: executor_fee,
-            dvns: dvn_fees,
+            executor: worker_fees.executor,
+            dvns: worker_fees.dvns,
             treasury: Some(TreasuryFee {
                 treasury: receiver,
                 fee: treasury_fee,
@@ -48,13 +48,42 @@ impl SetConfig<'_> {
                 )?;
             },
             Config::ReceiveUln(config) => {
-                ctx.accounts.receive_config.uln.set_config(config)?;
-
-                // get ULN config again as a catch all to ensure the config is valid
-                UlnConfig::get_config(
-                    &ctx.accounts.default_receive_config.uln,
-                    &ctx.accounts.receive_config.uln,
-                )?;
+                // the previous config without a grace period is no longer valid
+                ctx.accounts.receive_config.set_timeout(UlnConfigTimeout::default())?;
+                set_receive_uln_config(ctx, config)?;
+            },
+            Config::ReceiveUlnWithGracePeriod(config) => {
+                // keep the previous config and dvn set for the grace period
+                let receive_config = &mut ctx.accounts.receive_config;
+                let timeout =
+                    receive_config.grace_period_timeout(config.grace_period, Clock::get()?.slot)?;
+                receive_config.set_timeout(timeout)?;
+                receive_config.set_dvn_set(config.dvn_set)?;
+                set_receive_uln_config(ctx, &config.uln)?;
+            },
+            Config::ReceiveUlnTimeout(expiry) => {
+                let receive_config = &mut ctx.accounts.receive_config;
+                let timeout = if *expiry > 0 {
+                    // only the expiry of an active timeout can be changed
+                    let slot = Clock::get()?.slot;
+                    let timeout = receive_config.timeout();
+                    require!(*expiry > slot && timeout.is_active(slot), UlnError::InvalidExpiry);
+                    UlnConfigTimeout { expiry: *expiry, ..timeout.clone() }
+                } else {
+                    UlnConfigTimeout::default()
+                };
+                receive_config.set_timeout(timeout)?;
+            },
+            // the dvn set is validated when it is applied, as its dvns can be rewritten
+            Config::SendDvnSet(dvn_set) => {
+                ctx.accounts.send_config.set_dvn_set(*dvn_set)?;
+            },
+            Config::SendWorkerMaxFees(max_fees) => {
+                ctx.accounts.send_config.set_max_fees(max_fees)?;
+            },
+            Config::ReceiveDvnSet(dvn_set) => {
+                ctx.accounts.receive_config.set_timeout(UlnConfigTimeout::default())?;
+                ctx.accounts.receive_config.set_dvn_set(*dvn_set)?;
             },
         }
         emit_cpi!(ConfigSetEvent { eid: params.eid, oapp: params.oapp, config: config.clone() });
@@ -62,23 +91,64 @@ impl SetConfig<'_> {
     }
 }
 
+fn set_receive_uln_config(ctx: &mut Context<SetConfig>, config: &UlnConfig) -> Result<()> {
+    ctx.accounts.receive_config.uln.set_config(config)?;
+
+    // get ULN config again as a catch all to ensure the config is valid
+    UlnConfig::get_config(
+        &ctx.accounts.default_receive_config.uln,
+        &ctx.accounts.receive_config.uln,
+    )?;
+    Ok(())
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub enum Config {
     SendUln(UlnConfig),
     ReceiveUln(UlnConfig),
     Executor(ExecutorConfig),
+    SendDvnSet(Option<Pubkey>),
+    ReceiveDvnSet(Option<Pubkey>),
+    ReceiveUlnWithGracePeriod(ReceiveUlnConfigWithGracePeriod),
+    ReceiveUlnTimeout(u64), // the expiry slot of the previous receive config, 0 to remove it
+    SendWorkerMaxFees(Vec<WorkerMaxFee>),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReceiveUlnConfigWithGracePeriod {
+    pub uln: UlnConfig,
+    pub dvn_set: Option<Pubkey>,
+    pub grace_period: u64, // slots to keep verifying with the previous config
 }
 
 impl Config {
     pub const EXECUTOR: u32 = 1;
     pub const SEND_ULN: u32 = 2;
     pub const RECEIVE_ULN: u32 = 3;
+    pub const SEND_DVN_SET: u32 = 4;
+    pub const RECEIVE_DVN_SET: u32 = 5;
+    pub const RECEIVE_ULN_WITH_GRACE_PERIOD: u32 = 6;
+    pub const RECEIVE_ULN_TIMEOUT: u32 = 7;
+    pub const SEND_WORKER_MAX_FEES: u32 = 8;
 
     pub fn deserialize(config_type: u32, mut config: &[u8]) -> Result<Self> {
         match config_type {
             Self::EXECUTOR => Ok(Self::Executor(ExecutorConfig::deserialize(&mut config)?)),
             Self::SEND_ULN => Ok(Self::SendUln(UlnConfig::deserialize(&mut config)?)),
             Self::RECEIVE_ULN => Ok(Self::ReceiveUln(UlnConfig::deserialize(&mut config)?)),
+            Self::SEND_DVN_SET => Ok(Self::SendDvnSet(Option::<Pubkey>::deserialize(&mut config)?)),
+            Self::RECEIVE_DVN_SET => {
+                Ok(Self::ReceiveDvnSet(Option::<Pubkey>::deserialize(&mut config)?))
+            },
+            Self::RECEIVE_ULN_WITH_GRACE_PERIOD => Ok(Self::ReceiveUlnWithGracePeriod(
+                ReceiveUlnConfigWithGracePeriod::deserialize(&mut config)?,
+            )),
+            Self::RECEIVE_ULN_TIMEOUT => {
+                Ok(Self::ReceiveUlnTimeout(u64::deserialize(&mut config)?))
+            },
+            Self::SEND_WORKER_MAX_FEES => {
+                Ok(Self::SendWorkerMaxFees(Vec::<WorkerMaxFee>::deserialize(&mut config)?))
+            },
             _ => Err(UlnError::InvalidConfigType.into()),
         }
     }
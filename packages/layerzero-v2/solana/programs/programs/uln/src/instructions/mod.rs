@@ -1,7 +1,11 @@
 pub mod admin;
 pub mod dvn;
+pub mod dvn_set;
 pub mod endpoint;
+pub mod worker;
 
 pub use admin::*;
 pub use dvn::*;
+pub use dvn_set::*;
 pub use endpoint::*;
+pub use worker::*;
@@ -0,0 +1,136 @@
+use crate::*;
+
+/// Anyone can claim the fees accrued in the fee ledger shards, passed as the remaining accounts,
+/// as they can only be sent to the worker.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    /// The worker config account to receive the accrued fees
+    #[account(mut)]
+    pub worker: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [CLAIMED_FEES_SEED, worker.key.as_ref()],
+        bump = claimed_fees.bump
+    )]
+    pub claimed_fees: Account<'info, ClaimedFees>,
+}
+
+impl ClaimFees<'_> {
+    pub fn apply<'c: 'info, 'info>(
+        ctx: &mut Context<'_, '_, 'c, 'info, ClaimFees<'info>>,
+    ) -> Result<()> {
+        let required_lamports = Rent::get()?.minimum_balance(8 + FeeLedger::INIT_SPACE);
+        let mut claimed = LedgerFees::default();
+        let amount = claim_fee_ledgers(
+            &ctx.accounts.worker.key(),
+            ctx.remaining_accounts,
+            required_lamports,
+            &mut claimed,
+        )?;
+        ctx.accounts.worker.add_lamports(amount)?;
+        for fee in &claimed.fees {
+            ctx.accounts.claimed_fees.fees.add(fee.eid, fee.amount);
+        }
+
+        emit_cpi!(FeesClaimedEvent {
+            worker: ctx.accounts.worker.key(),
+            amount,
+            fees: claimed.fees
+        });
+
+        Ok(())
+    }
+}
+
+/// Takes the lamports over the required lamports from the fee ledger shards of the worker, and
+/// returns the amount taken. The amount taken from a shard is attributed to its unclaimed fees in
+/// the order of their eids, counted as claimed in the shard and added to `claimed` by destination.
+pub(crate) fn claim_fee_ledgers(
+    worker: &Pubkey,
+    fee_ledger_accs: &[AccountInfo],
+    required_lamports: u64,
+    claimed: &mut LedgerFees,
+) -> Result<u64> {
+    let mut amount: u64 = 0;
+    for fee_ledger_acc in fee_ledger_accs {
+        require!(fee_ledger_acc.owner == &ID, ErrorCode::AccountOwnedByWrongProgram);
+        let mut fee_ledger =
+            FeeLedger::try_deserialize(&mut &fee_ledger_acc.try_borrow_data()?[..])?;
+        require!(fee_ledger.worker == *worker, UlnError::InvalidFeeLedger);
+
+        let surplus_lamports = fee_ledger_acc.get_lamports().saturating_sub(required_lamports);
+        fee_ledger_acc.sub_lamports(surplus_lamports)?;
+        amount += surplus_lamports;
+
+        let mut remaining = surplus_lamports;
+        for fee in &fee_ledger.fees.fees {
+            let unclaimed = fee.amount.saturating_sub(fee_ledger.claimed_fees.get(fee.eid));
+            let part = unclaimed.min(remaining);
+            if part > 0 && fee_ledger.claimed_fees.add(fee.eid, part) {
+                claimed.add(fee.eid, part);
+                remaining -= part;
+            }
+        }
+        fee_ledger.try_serialize(&mut &mut fee_ledger_acc.try_borrow_mut_data()?[..])?;
+    }
+    Ok(amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fee_ledger::tests::fee_ledger_data;
+
+    #[test]
+    fn test_claim_fee_ledgers() {
+        let (key1, key2, worker) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut lamports1, mut lamports2) = (1000 + 100, 1000 + 50);
+        let mut data1 = fee_ledger_data(
+            worker,
+            vec![EidFee { eid: 1, amount: 60 }, EidFee { eid: 2, amount: 60 }],
+        );
+        let mut data2 = fee_ledger_data(worker, vec![EidFee { eid: 2, amount: 50 }]);
+        let shard1 =
+            AccountInfo::new(&key1, false, true, &mut lamports1, &mut data1[..], &ID, false, 0);
+        let shard2 =
+            AccountInfo::new(&key2, false, true, &mut lamports2, &mut data2[..], &ID, false, 0);
+
+        // a shard passed twice is only claimed once
+        let accounts = [shard1.clone(), shard2.clone(), shard1.clone()];
+        let mut claimed = LedgerFees::default();
+        assert_eq!(claim_fee_ledgers(&worker, &accounts, 1000, &mut claimed).unwrap(), 150);
+        assert_eq!(shard1.get_lamports(), 1000);
+        assert_eq!(shard2.get_lamports(), 1000);
+        assert_eq!(claim_fee_ledgers(&worker, &accounts, 1000, &mut claimed).unwrap(), 0);
+
+        // the claimed amount is attributed to the unclaimed fees of each shard in eid order
+        assert_eq!(
+            claimed.fees,
+            vec![EidFee { eid: 1, amount: 60 }, EidFee { eid: 2, amount: 90 }]
+        );
+        let fee_ledger =
+            FeeLedger::try_deserialize(&mut &shard1.try_borrow_data().unwrap()[..]).unwrap();
+        assert_eq!(
+            fee_ledger.claimed_fees.fees,
+            vec![EidFee { eid: 1, amount: 60 }, EidFee { eid: 2, amount: 40 }]
+        );
+
+        // the lamports over the unclaimed fees are claimed without attribution
+        shard1.add_lamports(50).unwrap();
+        let mut claimed = LedgerFees::default();
+        assert_eq!(claim_fee_ledgers(&worker, &accounts[..1], 1000, &mut claimed).unwrap(), 50);
+        assert_eq!(claimed.fees, vec![EidFee { eid: 2, amount: 20 }]);
+
+        // the shards of other workers are rejected
+        let result = claim_fee_ledgers(&Pubkey::new_unique(), &accounts, 1000, &mut claimed);
+        assert_eq!(result.unwrap_err(), UlnError::InvalidFeeLedger.into());
+
+        // so are the accounts other than the fee ledgers
+        let (mut lamports3, mut data3) = (2000, vec![0; 8 + FeeLedger::INIT_SPACE]);
+        let other =
+            AccountInfo::new(&key1, false, true, &mut lamports3, &mut data3[..], &ID, false, 0);
+        assert!(claim_fee_ledgers(&worker, &[other], 1000, &mut claimed).is_err());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: InitFeeLedgerParams)]
+pub struct InitFeeLedger<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [FEE_LEDGER_SEED, &params.worker.to_bytes(), &[params.shard]],
+        bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    /// The fees claimed to the worker, created with its first fee ledger shard
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ClaimedFees::INIT_SPACE,
+        seeds = [CLAIMED_FEES_SEED, &params.worker.to_bytes()],
+        bump
+    )]
+    pub claimed_fees: Account<'info, ClaimedFees>,
+    pub system_program: Program<'info, System>,
+}
+
+impl InitFeeLedger<'_> {
+    pub fn apply(ctx: &mut Context<InitFeeLedger>, params: &InitFeeLedgerParams) -> Result<()> {
+        ctx.accounts.fee_ledger.worker = params.worker;
+        ctx.accounts.fee_ledger.shard = params.shard;
+        ctx.accounts.fee_ledger.bump = ctx.bumps.fee_ledger;
+        ctx.accounts.claimed_fees.worker = params.worker;
+        ctx.accounts.claimed_fees.bump = ctx.bumps.claimed_fees;
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct InitFeeLedgerParams {
+    pub worker: Pubkey,
+    pub shard: u8,
+}
@@ -0,0 +1,5 @@
+pub mod claim_fees;
+pub mod init_fee_ledger;
+
+pub use claim_fees::*;
+pub use init_fee_ledger::*;
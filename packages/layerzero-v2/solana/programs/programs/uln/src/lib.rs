@@ -9,8 +9,8 @@ use errors::*;
 use events::*;
 use instructions::*;
 use messagelib_helper::messagelib_interface::{
-    InitConfigParams, MessagingFee, QuoteParams, SendParams, SendWithLzTokenParams,
-    SetConfigParams, Version,
+    InitConfigParams, MessagingFee, MessagingFeeDetail, QuoteParams, SendParams,
+    SendWithLzTokenParams, SetConfigParams, VerifiableParams, Version,
 };
 use options_codec::*;
 use solana_helper::program_id_from_env;
@@ -27,8 +27,13 @@ pub const ULN_SEED: &[u8] = messagelib_helper::MESSAGE_LIB_SEED;
 pub const SEND_CONFIG_SEED: &[u8] = b"SendConfig";
 pub const RECEIVE_CONFIG_SEED: &[u8] = b"ReceiveConfig";
 pub const CONFIRMATIONS_SEED: &[u8] = b"Confirmations";
+pub const FEE_LEDGER_SEED: &[u8] = b"FeeLedger";
+pub const CLAIMED_FEES_SEED: &[u8] = b"ClaimedFees";
+pub const DVN_SET_SEED: &[u8] = b"DvnSet";
+pub const DVN_SIGNERS_SEED: &[u8] = b"DvnSigners";
 
 pub const BPS_DENOMINATOR: u64 = 10000;
+pub const LZ_TOKEN_PRICE_RATIO_DENOMINATOR: u128 = u128::pow(10, 20);
 
 #[program]
 pub mod uln {
@@ -68,10 +73,21 @@ pub mod uln {
         SetTreasury::apply(&mut ctx, &params)
     }
 
+    pub fn set_treasury_program(
+        mut ctx: Context<SetTreasuryProgram>,
+        params: SetTreasuryProgramParams,
+    ) -> Result<()> {
+        SetTreasuryProgram::apply(&mut ctx, &params)
+    }
+
     pub fn withdraw_rent(mut ctx: Context<WithdrawRent>, params: WithdrawRentParams) -> Result<()> {
         WithdrawRent::apply(&mut ctx, &params)
     }
 
+    pub fn migrate_uln_settings(mut ctx: Context<MigrateUlnSettings>) -> Result<()> {
+        MigrateUlnSettings::apply(&mut ctx)
+    }
+
     /// --------------------------- Endpoint Instructions ---------------------------
     pub fn init_config(mut ctx: Context<InitConfig>, params: InitConfigParams) -> Result<()> {
         InitConfig::apply(&mut ctx, &params)
@@ -81,10 +97,19 @@ pub mod uln {
         SetConfig::apply(&mut ctx, &params)
     }
 
+    /// not called by the endpoint, but extends the config accounts created by init_config()
+    pub fn extend_config(mut ctx: Context<ExtendConfig>, params: ExtendConfigParams) -> Result<()> {
+        ExtendConfig::apply(&mut ctx, &params)
+    }
+
     pub fn quote(ctx: Context<Quote>, params: QuoteParams) -> Result<MessagingFee> {
         Quote::apply(&ctx, &params)
     }
 
+    pub fn quote_detailed(ctx: Context<Quote>, params: QuoteParams) -> Result<MessagingFeeDetail> {
+        Quote::quote_detailed(&ctx, &params)
+    }
+
     pub fn send<'c: 'info, 'info>(
         mut ctx: Context<'_, '_, 'c, 'info, Send<'info>>,
         params: SendParams,
@@ -118,6 +143,61 @@ pub mod uln {
     ) -> Result<()> {
         CommitVerification::apply(&mut ctx, &params)
     }
+
+    pub fn commit_verification_with_signatures(
+        mut ctx: Context<CommitVerificationWithSignatures>,
+        params: CommitVerificationWithSignaturesParams,
+    ) -> Result<()> {
+        CommitVerificationWithSignatures::apply(&mut ctx, &params)
+    }
+
+    pub fn init_dvn_signers(
+        mut ctx: Context<InitDvnSigners>,
+        params: InitDvnSignersParams,
+    ) -> Result<()> {
+        InitDvnSigners::apply(&mut ctx, &params)
+    }
+
+    pub fn set_dvn_signers(
+        mut ctx: Context<SetDvnSigners>,
+        params: SetDvnSignersParams,
+    ) -> Result<()> {
+        SetDvnSigners::apply(&mut ctx, &params)
+    }
+
+    pub fn verifiable(ctx: Context<Verifiable>, params: VerifiableParams) -> Result<bool> {
+        Verifiable::apply(&ctx, &params)
+    }
+
+    /// --------------------------- DVN Set Instructions ---------------------------
+    pub fn init_dvn_set(mut ctx: Context<InitDvnSet>, params: InitDvnSetParams) -> Result<()> {
+        InitDvnSet::apply(&mut ctx, &params)
+    }
+
+    pub fn write_dvn_set_page(
+        mut ctx: Context<WriteDvnSetPage>,
+        params: WriteDvnSetPageParams,
+    ) -> Result<()> {
+        WriteDvnSetPage::apply(&mut ctx, &params)
+    }
+
+    pub fn set_dvn_set(mut ctx: Context<SetDvnSet>, params: SetDvnSetParams) -> Result<()> {
+        SetDvnSet::apply(&mut ctx, &params)
+    }
+
+    /// --------------------------- Worker Instructions ---------------------------
+    pub fn init_fee_ledger(
+        mut ctx: Context<InitFeeLedger>,
+        params: InitFeeLedgerParams,
+    ) -> Result<()> {
+        InitFeeLedger::apply(&mut ctx, &params)
+    }
+
+    pub fn claim_fees<'c: 'info, 'info>(
+        mut ctx: Context<'_, '_, 'c, 'info, ClaimFees<'info>>,
+    ) -> Result<()> {
+        ClaimFees::apply(&mut ctx)
+    }
 }
 
 #[derive(Accounts)]
@@ -1,5 +1,5 @@
 use crate::*;
-use messagelib_helper::utils::bytes_lib::BytesUtils;
+use messagelib_helper::utils::bytes_lib::{BytesCursor, BytesUtils};
 use std::collections::HashMap;
 use worker_interface::LzOption;
 
@@ -16,47 +16,38 @@ pub const EXECUTOR_OPTION_TYPE_LZRECEIVE: u8 = 1;
 pub const EXECUTOR_OPTION_TYPE_NATIVE_DROP: u8 = 2;
 
 pub fn decode_options(options: &[u8]) -> Result<(Vec<LzOption>, DVNOptions)> {
-    let mut executor_options = Vec::new();
-    let mut dvn_options = DVNOptions::new();
+    let mut cursor = BytesCursor::new(options);
 
     // the first 2 bytes is the format type
-    let format_type = options.to_u16(0);
+    let format_type = cursor.read_u16().ok_or(UlnError::TruncatedOptionHeader)?;
     if format_type < TYPE_3 {
-        executor_options = convert_legacy_options(format_type, &options)?;
-        Ok((executor_options, dvn_options))
+        let executor_options = convert_legacy_options(format_type, options)?;
+        Ok((executor_options, DVNOptions::new()))
     } else if format_type == TYPE_3 {
         // type3 options: [worker_option][worker_option]...
         // worker_option: [worker_id][option_size][option]
         // option: [option_type][params]
         // worker_id: uint8, option_size: uint16, option: bytes, option_type: uint8, params: bytes
-        let mut cursor = 2;
-        while cursor < options.len() {
-            let worker_id = options.to_u8(cursor);
-            cursor += 1;
-            let option_size = options.to_u16(cursor) as usize;
-            cursor += 2;
+        let mut executor_options = Vec::new();
+        let mut dvn_options = DVNOptions::new();
+        while !cursor.is_empty() {
+            let worker_id = cursor.read_u8().ok_or(UlnError::TruncatedOptionHeader)?;
+            let option_size = cursor.read_u16().ok_or(UlnError::TruncatedOptionHeader)? as usize;
+            require!(option_size > 0, UlnError::ZeroSizeOption);
+            let option = cursor.read_bytes(option_size).ok_or(UlnError::OptionOverrun)?;
 
             match worker_id {
                 EXECUTOR_WORKER_ID => {
-                    let option_type = options.to_u8(cursor);
-                    let option_params = &options[cursor + 1..cursor + option_size];
-                    cursor += option_size;
-                    executor_options.push(LzOption { option_type, params: option_params.to_vec() })
+                    executor_options
+                        .push(LzOption { option_type: option[0], params: option[1..].to_vec() });
                 },
                 DVN_WORKER_ID => {
-                    let idx = options.to_u8(cursor);
-                    let option_type = options.to_u8(cursor + 1);
-                    let option_params = &options[cursor + 2..cursor + option_size];
-                    cursor += option_size;
-
-                    if let Some(options) = dvn_options.get_mut(&idx) {
-                        options.push(LzOption { option_type, params: option_params.to_vec() });
-                    } else {
-                        dvn_options.insert(
-                            idx,
-                            vec![LzOption { option_type, params: option_params.to_vec() }],
-                        );
-                    }
+                    // dvn option: [dvn_idx][option_type][params]
+                    require!(option_size > 1, UlnError::InvalidDvnOptionSize);
+                    dvn_options
+                        .entry(option[0])
+                        .or_default()
+                        .push(LzOption { option_type: option[1], params: option[2..].to_vec() });
                 },
                 _ => return Err(UlnError::InvalidWorkerId.into()),
             }
@@ -86,17 +77,16 @@ fn convert_legacy_options(format_type: u16, options: &[u8]) -> Result<Vec<LzOpti
             }])
         },
         TYPE_2 => {
-            require!(options.len() > 66 && options.len() <= 98, UlnError::InvalidType2Size);
+            require!(options.len() == 98, UlnError::InvalidType2Size);
             require!(options.to_u128(2) == 0, UlnError::ExceededU128); // the gas amount should be <= u128::MAX
             let execution_gas = options.to_u128(18);
             require!(options.to_u128(34) == 0, UlnError::ExceededU128); // the native drop amount should be <= u128::MAX
             let native_drop_amount = options.to_u128(50);
             let receiver_bytes = &options[66..];
 
-            // convert receiver to [0;32]
             let mut native_drop_params = Vec::with_capacity(48); // 16 + 32
             native_drop_params.extend_from_slice(&native_drop_amount.to_be_bytes());
-            native_drop_params.extend_from_slice(&receiver_bytes[..32]);
+            native_drop_params.extend_from_slice(receiver_bytes);
 
             Ok(vec![
                 LzOption {
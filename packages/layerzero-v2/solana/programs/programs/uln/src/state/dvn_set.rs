@@ -0,0 +1,191 @@
+use crate::*;
+
+// the accounts a transaction can lock, including the ones looked up from address lookup tables
+pub const MAX_TX_ACCOUNT_LOCKS: usize = 64;
+// the accounts of a commit_verification transaction besides the confirmation accounts of the dvns:
+// the payer, the compute budget and uln programs, the receive configs, the uln settings, the dvn
+// set, and the endpoint program with the accounts of its verify instruction
+pub const COMMIT_VERIFICATION_BASE_ACCOUNTS_LEN: usize = 14;
+// the dvns, required and optional, whose confirmation accounts fit in a commit_verification
+// transaction looking them up from address lookup tables
+pub const DVN_SET_MAX_LEN: u8 =
+    (MAX_TX_ACCOUNT_LOCKS - COMMIT_VERIFICATION_BASE_ACCOUNTS_LEN) as u8;
+// the dvns written by a write_dvn_set_page instruction, which fits in a transaction with set_dvn_set
+pub const DVN_SET_PAGE_LEN: usize = DVN_MAX_LEN as usize;
+
+/// The optional dvns shared by the oapp configs referencing it by address. The dvns are written
+/// by the authority, who therefore controls the verification of the oapps referencing the set.
+/// The sends of the oapps take the 4 quote accounts of each dvn, of which the dvn program and the
+/// price feed accounts are shared by the dvns deployed with them, so the cost of a send is bounded
+/// by its transaction, which the authority sizes the set for, rather than by the set.
+#[account]
+#[derive(InitSpace)]
+pub struct DvnSet {
+    pub authority: Pubkey,
+    pub id: u32,
+    pub bump: u8,
+    pub optional_dvn_threshold: u8,
+    #[max_len(DVN_SET_MAX_LEN)]
+    pub dvns: Vec<Pubkey>, // sorted PDAs of DVN programs
+    #[max_len(DVN_SET_MAX_LEN)]
+    pub written_dvns: Vec<Pubkey>, // the pages written to replace the dvns by set_dvn_set()
+}
+
+impl DvnSet {
+    /// Writes a page of the dvns to be set, the first page discarding the pages written before
+    pub fn write_page(&mut self, page: u8, dvns: &[Pubkey]) -> Result<()> {
+        require!(dvns.len() <= DVN_SET_PAGE_LEN, UlnError::InvalidOptionalDVNCount);
+        if page == 0 {
+            self.written_dvns.clear();
+        }
+        require!(
+            self.written_dvns.len() == page as usize * DVN_SET_PAGE_LEN,
+            UlnError::InvalidDvnSetPage
+        );
+        require!(
+            self.written_dvns.len() + dvns.len() <= DVN_SET_MAX_LEN as usize,
+            UlnError::InvalidOptionalDVNCount
+        );
+        self.written_dvns.extend_from_slice(dvns);
+        Ok(())
+    }
+
+    /// Replaces the dvns with the written pages and the threshold at once, so that the configs
+    /// referencing the set never apply a partly written set
+    pub fn set_written_dvns(&mut self, optional_dvn_threshold: u8) -> Result<()> {
+        let dvns = std::mem::take(&mut self.written_dvns);
+        UlnConfig::assert_no_duplicates(&dvns)?;
+        require!(
+            optional_dvn_threshold > 0 && optional_dvn_threshold as usize <= dvns.len(),
+            UlnError::InvalidOptionalDVNThreshold
+        );
+        self.dvns = dvns;
+        self.optional_dvn_threshold = optional_dvn_threshold;
+        Ok(())
+    }
+}
+
+impl UlnConfig {
+    /// Replaces the optional dvns with the dvns of the dvn set, asserting all the dvns fit in a
+    /// commit_verification transaction
+    pub fn apply_dvn_set(&mut self, dvn_set: &DvnSet) -> Result<()> {
+        require!(
+            self.required_dvns.len() + dvn_set.dvns.len() <= DVN_SET_MAX_LEN as usize,
+            UlnError::InvalidOptionalDVNCount
+        );
+        self.optional_dvns = dvn_set.dvns.clone();
+        self.optional_dvn_count = dvn_set.dvns.len() as u8;
+        self.optional_dvn_threshold = dvn_set.optional_dvn_threshold;
+        require!(
+            self.optional_dvn_threshold > 0
+                && self.optional_dvn_threshold <= self.optional_dvn_count,
+            UlnError::InvalidOptionalDVNThreshold
+        );
+        Ok(())
+    }
+}
+
+/// Splits the dvn set account off the front of the remaining accounts if the config references a
+/// dvn set, so that the fixed accounts of the instructions stay the same and can be looked up from
+/// an address lookup table.
+pub fn split_dvn_set_account<'a, 'info>(
+    dvn_set: Option<Pubkey>,
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(Option<DvnSet>, &'a [AccountInfo<'info>])> {
+    let address = match dvn_set {
+        Some(address) => address,
+        None => return Ok((None, accounts)),
+    };
+    let (dvn_set_acc, rest) = accounts.split_first().ok_or(UlnError::InvalidAccountLength)?;
+    require!(dvn_set_acc.key() == address && dvn_set_acc.owner == &ID, UlnError::InvalidDvnSet);
+    let dvn_set = DvnSet::try_deserialize(&mut &dvn_set_acc.try_borrow_data()?[..])?;
+    Ok((Some(dvn_set), rest))
+}
+
+utils::generate_account_size_test!(DvnSet, dvn_set_test);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dvn_set(threshold: u8, len: usize) -> DvnSet {
+        let mut dvns = (0..len).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        dvns.sort();
+        DvnSet {
+            authority: Pubkey::new_unique(),
+            id: 0,
+            bump: 0,
+            optional_dvn_threshold: threshold,
+            dvns,
+            written_dvns: vec![],
+        }
+    }
+
+    #[test]
+    fn test_apply_dvn_set() {
+        let dvn_set = dvn_set(5, DVN_SET_MAX_LEN as usize - 1);
+        let mut config = UlnConfig {
+            confirmations: 1,
+            required_dvn_count: 1,
+            optional_dvn_count: 1,
+            optional_dvn_threshold: 1,
+            required_dvns: vec![Pubkey::new_unique()],
+            optional_dvns: vec![Pubkey::new_unique()],
+        };
+        config.apply_dvn_set(&dvn_set).unwrap();
+        assert_eq!(config.optional_dvns, dvn_set.dvns);
+        assert_eq!(config.optional_dvn_count, DVN_SET_MAX_LEN - 1);
+        assert_eq!(config.optional_dvn_threshold, 5);
+        assert_eq!(config.required_dvns.len(), 1);
+
+        // the required dvns count towards the dvns of a commit_verification transaction
+        let dvn_set = self::dvn_set(5, DVN_SET_MAX_LEN as usize);
+        assert!(config.apply_dvn_set(&dvn_set).is_err());
+    }
+
+    #[test]
+    fn test_apply_dvn_set_invalid_threshold() {
+        let mut config = UlnConfig::default();
+        assert!(config.apply_dvn_set(&dvn_set(0, 2)).is_err());
+        assert!(config.apply_dvn_set(&dvn_set(3, 2)).is_err());
+    }
+
+    #[test]
+    fn test_write_dvn_set_pages() {
+        let mut dvns = (0..DVN_SET_MAX_LEN).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        dvns.sort();
+        let mut set = dvn_set(1, 1);
+        let old_dvns = set.dvns.clone();
+
+        // more dvns than DVN_MAX_LEN are written page by page
+        for (page, chunk) in dvns.chunks(DVN_SET_PAGE_LEN).enumerate() {
+            set.write_page(page as u8, chunk).unwrap();
+            // the dvns are not replaced until the set is set
+            assert_eq!(set.dvns, old_dvns);
+        }
+        set.set_written_dvns(3).unwrap();
+        assert_eq!(set.dvns, dvns);
+        assert_eq!(set.optional_dvn_threshold, 3);
+        assert!(set.written_dvns.is_empty());
+
+        // the pages are written in order, and the first page restarts the writing
+        set.write_page(0, &dvns[..DVN_SET_PAGE_LEN]).unwrap();
+        let result = set.write_page(2, &dvns[DVN_SET_PAGE_LEN..DVN_SET_PAGE_LEN + 1]);
+        assert_eq!(result.unwrap_err(), UlnError::InvalidDvnSetPage.into());
+        set.write_page(0, &dvns[..2]).unwrap();
+        let result = set.write_page(1, &dvns[2..4]);
+        assert_eq!(result.unwrap_err(), UlnError::InvalidDvnSetPage.into());
+        set.set_written_dvns(2).unwrap();
+        assert_eq!(set.dvns, dvns[..2].to_vec());
+
+        // a page is at most DVN_SET_PAGE_LEN dvns
+        let result = set.write_page(0, &dvns[..DVN_SET_PAGE_LEN + 1]);
+        assert_eq!(result.unwrap_err(), UlnError::InvalidOptionalDVNCount.into());
+
+        // the written dvns are sorted and their threshold is valid
+        set.write_page(0, &[dvns[1], dvns[0]]).unwrap();
+        assert_eq!(set.set_written_dvns(1).unwrap_err(), UlnError::Unsorted.into());
+        set.write_page(0, &dvns[..2]).unwrap();
+        assert!(set.set_written_dvns(3).is_err());
+    }
+}
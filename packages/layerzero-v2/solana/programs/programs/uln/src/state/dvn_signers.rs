@@ -0,0 +1,207 @@
+use crate::*;
+use anchor_lang::solana_program::{
+    keccak::hashv as keccak256,
+    secp256k1_recover::{secp256k1_recover, SECP256K1_PUBLIC_KEY_LENGTH},
+};
+use std::collections::HashSet;
+
+pub const DVN_SIGNERS_MAX_LEN: usize = 7;
+/// The first 64 bytes are the signature, and the last byte is the recovery ID.
+pub const SECP256K1_SIGNATURE_BYTES: usize = 65;
+
+/// The signers of a dvn, set by the dvn. A quorum of their signatures of a packet verifies it for
+/// the dvn in commit_verification_with_signatures(), without the confirmations account of the dvn.
+#[account]
+#[derive(InitSpace)]
+pub struct DvnSigners {
+    pub dvn: Pubkey,
+    pub bump: u8,
+    pub quorum: u8,
+    #[max_len(DVN_SIGNERS_MAX_LEN)]
+    pub secp256k1_signers: Vec<[u8; SECP256K1_PUBLIC_KEY_LENGTH]>,
+    #[max_len(DVN_SIGNERS_MAX_LEN)]
+    pub ed25519_signers: Vec<Pubkey>,
+}
+
+impl DvnSigners {
+    pub fn set(&mut self, params: DvnSignersParams) -> Result<()> {
+        self.quorum = params.quorum;
+        self.secp256k1_signers = params.secp256k1_signers;
+        self.ed25519_signers = params.ed25519_signers;
+        self.sanity_check()
+    }
+
+    pub fn sanity_check(&self) -> Result<()> {
+        let signers_len = self.secp256k1_signers.len() + self.ed25519_signers.len();
+        require!(
+            signers_len > 0 && signers_len <= DVN_SIGNERS_MAX_LEN,
+            UlnError::InvalidDvnSignersLen
+        );
+        require!(
+            self.quorum > 0 && self.quorum as usize <= signers_len,
+            UlnError::InvalidDvnSignersQuorum
+        );
+        let mut unique = HashSet::new();
+        require!(
+            self.secp256k1_signers.iter().all(|signer| unique.insert(&signer[..]))
+                && self.ed25519_signers.iter().all(|signer| unique.insert(signer.as_ref())),
+            UlnError::DuplicateDvnSigner
+        );
+        Ok(())
+    }
+
+    /// Returns true if the quorum of the signers signed the hash. The ed25519 signatures are the
+    /// signers and messages verified by the ed25519 program in the transaction, which may be of
+    /// the signers of other dvns. The signatures of any kind that are not of a signer of the dvn,
+    /// or repeat one, are not counted.
+    pub fn signed(
+        &self,
+        hash: &[u8; 32],
+        secp256k1_signatures: &[[u8; SECP256K1_SIGNATURE_BYTES]],
+        ed25519_signatures: &[(Pubkey, Vec<u8>)],
+    ) -> bool {
+        let mut signed: HashSet<Vec<u8>> = HashSet::new();
+        for sig in secp256k1_signatures {
+            let recovery_id = sig[SECP256K1_SIGNATURE_BYTES - 1];
+            let recovered =
+                secp256k1_recover(&hash[..], recovery_id, &sig[..(SECP256K1_SIGNATURE_BYTES - 1)]);
+            if let Ok(pubkey) = recovered.map(|pubkey| pubkey.to_bytes()) {
+                if self.secp256k1_signers.contains(&pubkey) {
+                    signed.insert(pubkey.to_vec());
+                }
+            }
+        }
+        for (signer, message) in ed25519_signatures {
+            if message[..] == hash[..] && self.ed25519_signers.contains(signer) {
+                signed.insert(signer.to_bytes().to_vec());
+            }
+        }
+        self.quorum > 0 && signed.len() >= self.quorum as usize
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct DvnSignersParams {
+    pub quorum: u8,
+    pub secp256k1_signers: Vec<[u8; SECP256K1_PUBLIC_KEY_LENGTH]>,
+    pub ed25519_signers: Vec<Pubkey>,
+}
+
+/// The hash signed by the signers of a dvn to verify the packet with the confirmations for the
+/// dvn, so that the signatures for one dvn can not verify the packet for another dvn sharing signers
+pub fn verification_hash(
+    dvn: &Pubkey,
+    packet_header: &[u8],
+    payload_hash: &[u8; 32],
+    confirmations: u64,
+) -> [u8; 32] {
+    keccak256(&[
+        ID.as_ref(),
+        dvn.as_ref(),
+        packet_header,
+        &payload_hash[..],
+        &confirmations.to_be_bytes(),
+    ])
+    .to_bytes()
+}
+
+utils::generate_account_size_test!(DvnSigners, dvn_signers_test);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dvn_signers(quorum: u8, ed25519_signers: Vec<Pubkey>) -> DvnSigners {
+        DvnSigners {
+            dvn: Pubkey::new_unique(),
+            bump: 0,
+            quorum,
+            secp256k1_signers: vec![],
+            ed25519_signers,
+        }
+    }
+
+    #[test]
+    fn test_sanity_check() {
+        let signer = Pubkey::new_unique();
+        assert!(dvn_signers(1, vec![signer]).sanity_check().is_ok());
+        assert!(dvn_signers(0, vec![signer]).sanity_check().is_err());
+        assert!(dvn_signers(2, vec![signer]).sanity_check().is_err());
+        assert!(dvn_signers(1, vec![]).sanity_check().is_err());
+        assert!(dvn_signers(1, vec![signer, signer]).sanity_check().is_err());
+        let signers = (0..=DVN_SIGNERS_MAX_LEN).map(|_| Pubkey::new_unique()).collect();
+        assert!(dvn_signers(1, signers).sanity_check().is_err());
+    }
+
+    #[test]
+    fn test_signed_by_ed25519_signers() {
+        let (signer1, signer2) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let signers = dvn_signers(2, vec![signer1, signer2]);
+        let hash = verification_hash(&signers.dvn, &[1u8; 81], &[2u8; 32], 10);
+
+        let signatures = vec![(signer1, hash.to_vec()), (signer1, hash.to_vec())];
+        assert!(!signers.signed(&hash, &[], &signatures));
+
+        let signatures = vec![(signer1, hash.to_vec()), (signer2, [0u8; 32].to_vec())];
+        assert!(!signers.signed(&hash, &[], &signatures));
+
+        let signatures = vec![(signer1, hash.to_vec()), (Pubkey::new_unique(), hash.to_vec())];
+        assert!(!signers.signed(&hash, &[], &signatures));
+
+        let signatures = vec![(signer2, hash.to_vec()), (signer1, hash.to_vec())];
+        assert!(signers.signed(&hash, &[], &signatures));
+    }
+
+    #[test]
+    fn test_signed_by_secp256k1_signers() {
+        use secp256k1::{rand::rngs::OsRng, Message, Secp256k1, SecretKey};
+
+        let secp = Secp256k1::new();
+        let sign = |hash: &[u8; 32], secret_key: &SecretKey| {
+            let message = Message::from_digest(*hash);
+            let (recovery_id, compact) =
+                secp.sign_ecdsa_recoverable(&message, secret_key).serialize_compact();
+            let mut sig = [0u8; SECP256K1_SIGNATURE_BYTES];
+            sig[..64].copy_from_slice(&compact);
+            sig[64] = recovery_id.to_i32() as u8;
+            sig
+        };
+        let mut secret_keys = vec![];
+        let mut secp256k1_signers = vec![];
+        for _ in 0..3 {
+            let (secret_key, public_key) = secp.generate_keypair(&mut OsRng);
+            let mut signer = [0u8; SECP256K1_PUBLIC_KEY_LENGTH];
+            // without the 0x04 prefix of the uncompressed public key
+            signer.copy_from_slice(&public_key.serialize_uncompressed()[1..]);
+            secret_keys.push(secret_key);
+            secp256k1_signers.push(signer);
+        }
+        let ed25519_signer = Pubkey::new_unique();
+        let signers = DvnSigners {
+            dvn: Pubkey::new_unique(),
+            bump: 0,
+            quorum: 2,
+            secp256k1_signers: secp256k1_signers[..2].to_vec(),
+            ed25519_signers: vec![ed25519_signer],
+        };
+        let hash = verification_hash(&signers.dvn, &[1u8; 81], &[2u8; 32], 10);
+        let (sig1, sig2) = (sign(&hash, &secret_keys[0]), sign(&hash, &secret_keys[1]));
+
+        assert!(signers.signed(&hash, &[sig1, sig2], &[]));
+        // with the ed25519 signers
+        assert!(signers.signed(&hash, &[sig2], &[(ed25519_signer, hash.to_vec())]));
+        // the duplicate signatures are counted once
+        assert!(!signers.signed(&hash, &[sig1, sig1], &[]));
+        // the signatures of the signers of another dvn are not counted, like the ed25519 ones
+        let other_sig = sign(&hash, &secret_keys[2]);
+        assert!(!signers.signed(&hash, &[sig1, other_sig], &[]));
+        assert!(signers.signed(&hash, &[other_sig, sig1, sig2], &[]));
+        // and neither are the malformed signatures
+        assert!(!signers.signed(&hash, &[sig1, [0u8; SECP256K1_SIGNATURE_BYTES]], &[]));
+
+        // the signatures for another dvn with the same signers do not verify the packet
+        let other_hash = verification_hash(&Pubkey::new_unique(), &[1u8; 81], &[2u8; 32], 10);
+        let other_sigs = [sign(&other_hash, &secret_keys[0]), sign(&other_hash, &secret_keys[1])];
+        assert!(!signers.signed(&hash, &other_sigs, &[]));
+    }
+}
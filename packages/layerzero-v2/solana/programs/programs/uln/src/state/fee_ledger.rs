@@ -0,0 +1,156 @@
+use crate::*;
+
+pub const FEE_LEDGER_EIDS_MAX_LEN: usize = 16;
+
+/// Accrues the lamport fees of a worker on send, so that the worker config account does not have to
+/// be write-locked by every send. Each worker can have many shards to spread the write-locks of
+/// concurrent sends, and the accrued fees are claimed to the worker by claim_fees().
+#[account]
+#[derive(InitSpace)]
+pub struct FeeLedger {
+    pub worker: Pubkey,
+    pub shard: u8,
+    pub bump: u8,
+    pub fees: LedgerFees,
+    // the part of the fees claimed to the worker by claim_fees(), by destination
+    pub claimed_fees: LedgerFees,
+}
+
+/// The fees claimed to a worker from all its fee ledger shards by destination, at a fixed address
+/// per worker, so that the worker can attribute the lamports it receives by claim_fees().
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimedFees {
+    pub worker: Pubkey,
+    pub bump: u8,
+    pub fees: LedgerFees,
+}
+
+/// The lifetime fees accrued in the fee ledger by destination, for the revenue attribution of the
+/// worker. They are not reset when claimed, and the fees to the destinations after the first
+/// FEE_LEDGER_EIDS_MAX_LEN ones are accrued without attribution.
+#[derive(InitSpace, Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct LedgerFees {
+    #[max_len(FEE_LEDGER_EIDS_MAX_LEN)]
+    pub fees: Vec<EidFee>, // sorted by eid
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Debug)]
+pub struct EidFee {
+    pub eid: u32,
+    pub amount: u64,
+}
+
+impl LedgerFees {
+    /// The fee of the destination, zero if not added
+    pub fn get(&self, eid: u32) -> u64 {
+        self.fees
+            .binary_search_by_key(&eid, |f| f.eid)
+            .map_or(0, |index| self.fees[index].amount)
+    }
+
+    /// Adds the fee to the destination, returns false if the destination can not be added
+    pub fn add(&mut self, eid: u32, amount: u64) -> bool {
+        match self.fees.binary_search_by_key(&eid, |f| f.eid) {
+            Ok(index) => {
+                self.fees[index].amount = self.fees[index].amount.saturating_add(amount);
+                true
+            },
+            Err(index) => {
+                if self.fees.len() >= FEE_LEDGER_EIDS_MAX_LEN {
+                    return false;
+                }
+                self.fees.insert(index, EidFee { eid, amount });
+                true
+            },
+        }
+    }
+}
+
+/// Credits the fee of a packet to the destination in the fee ledger account
+pub(crate) fn credit_fee_ledger(fee_ledger_acc: &AccountInfo, eid: u32, amount: u64) -> Result<()> {
+    let mut fee_ledger = FeeLedger::try_deserialize(&mut &fee_ledger_acc.try_borrow_data()?[..])?;
+    if fee_ledger.fees.add(eid, amount) {
+        fee_ledger.try_serialize(&mut &mut fee_ledger_acc.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
+
+utils::generate_account_size_test!(FeeLedger, fee_ledger_test);
+utils::generate_account_size_test!(ClaimedFees, claimed_fees_test);
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ledger_fees_add() {
+        let mut fees = LedgerFees::default();
+        assert!(fees.add(2, 10));
+        assert!(fees.add(1, 5));
+        assert!(fees.add(2, u64::MAX));
+        assert_eq!(
+            fees.fees,
+            vec![EidFee { eid: 1, amount: 5 }, EidFee { eid: 2, amount: u64::MAX }]
+        );
+
+        for eid in 3..=FEE_LEDGER_EIDS_MAX_LEN as u32 {
+            assert!(fees.add(eid, 1));
+        }
+        assert!(!fees.add(100, 1));
+        assert!(fees.add(1, 1));
+        assert_eq!(fees.fees[0].amount, 6);
+        assert_eq!(fees.get(1), 6);
+        assert_eq!(fees.get(100), 0);
+    }
+
+    pub(crate) fn fee_ledger_data(worker: Pubkey, fees: Vec<EidFee>) -> Vec<u8> {
+        let fee_ledger = FeeLedger {
+            worker,
+            shard: 0,
+            bump: 0,
+            fees: LedgerFees { fees },
+            claimed_fees: LedgerFees::default(),
+        };
+        let mut data = vec![0; 8 + FeeLedger::INIT_SPACE];
+        fee_ledger.try_serialize(&mut &mut data[..]).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_credit_fee_ledger() {
+        let (key, worker) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut lamports = 0;
+        let mut data = fee_ledger_data(worker, vec![EidFee { eid: 2, amount: 10 }]);
+        let fee_ledger_acc =
+            AccountInfo::new(&key, false, true, &mut lamports, &mut data[..], &ID, false, 0);
+
+        credit_fee_ledger(&fee_ledger_acc, 1, 5).unwrap();
+        credit_fee_ledger(&fee_ledger_acc, 2, 7).unwrap();
+        let fee_ledger =
+            FeeLedger::try_deserialize(&mut &fee_ledger_acc.try_borrow_data().unwrap()[..])
+                .unwrap();
+        assert_eq!(fee_ledger.worker, worker);
+        assert_eq!(
+            fee_ledger.fees.fees,
+            vec![EidFee { eid: 1, amount: 5 }, EidFee { eid: 2, amount: 17 }]
+        );
+
+        // the fees to a destination over the max are not attributed
+        let fees = (1..=FEE_LEDGER_EIDS_MAX_LEN as u32).map(|eid| EidFee { eid, amount: 1 });
+        let mut data = fee_ledger_data(worker, fees.clone().collect());
+        let fee_ledger_acc =
+            AccountInfo::new(&key, false, true, &mut lamports, &mut data[..], &ID, false, 0);
+        credit_fee_ledger(&fee_ledger_acc, 100, 1).unwrap();
+        let fee_ledger =
+            FeeLedger::try_deserialize(&mut &fee_ledger_acc.try_borrow_data().unwrap()[..])
+                .unwrap();
+        assert_eq!(fee_ledger.fees.fees, fees.collect::<Vec<_>>());
+
+        // the account must be a fee ledger
+        let mut data = vec![0; 8 + FeeLedger::INIT_SPACE];
+        let other_acc =
+            AccountInfo::new(&key, false, true, &mut lamports, &mut data[..], &ID, false, 0);
+        assert!(credit_fee_ledger(&other_acc, 1, 1).is_err());
+    }
+}
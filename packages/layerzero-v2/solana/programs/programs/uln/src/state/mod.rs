@@ -1,5 +1,13 @@
 pub mod confirmations;
+pub mod dvn_set;
+pub mod dvn_signers;
+pub mod fee_ledger;
 pub mod uln;
+pub mod worker_max_fees;
 
 pub use confirmations::*;
+pub use dvn_set::*;
+pub use dvn_signers::*;
+pub use fee_ledger::*;
 pub use uln::*;
+pub use worker_max_fees::*;
@@ -1,5 +1,6 @@
 use crate::*;
 use std::cmp::Ordering;
+use utils::appended_field::{Appended, AppendedFields};
 
 #[account]
 #[derive(InitSpace)]
@@ -12,6 +13,47 @@ pub struct UlnSettings {
     // mutable
     pub admin: Pubkey,
     pub treasury: Option<Treasury>,
+    pub appended: Appended<UlnSettingsAppended>,
+}
+
+/// The fields appended to the uln settings after the account was created. They are read as the
+/// defaults until the account is migrated by migrate_uln_settings().
+#[derive(InitSpace, Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct UlnSettingsAppended {
+    // set with the treasury
+    pub treasury_fees: TreasuryFees,
+}
+
+impl AppendedFields for UlnSettingsAppended {
+    const DISCRIMINATOR: [u8; 8] = *b"UlnSetEx";
+    const VERSION: u8 = 1;
+}
+
+impl UlnSettings {
+    /// Sets the treasury and its fees, which are reset to the defaults without the treasury. The
+    /// fees other than the defaults require the account to be migrated.
+    pub fn set_treasury(&mut self, treasury: Option<Treasury>, fees: TreasuryFees) -> Result<()> {
+        let fees = if treasury.is_some() { fees } else { TreasuryFees::default() };
+        match self.appended.get_mut() {
+            Some(appended) => appended.treasury_fees = fees,
+            None => require!(fees == TreasuryFees::default(), UlnError::NotMigrated),
+        }
+        self.treasury = treasury;
+        Ok(())
+    }
+
+    pub fn treasury_fees(&self) -> &TreasuryFees {
+        &self.appended.treasury_fees
+    }
+
+    /// Asserts the signer is the admin or the treasury admin
+    pub fn assert_treasury_signer(&self, signer: &Pubkey) -> Result<()> {
+        if *signer != self.admin {
+            let treasury = self.treasury.as_ref().ok_or(UlnError::Unauthorized)?;
+            require!(Some(*signer) == treasury.admin, UlnError::Unauthorized);
+        }
+        Ok(())
+    }
 }
 
 #[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
@@ -25,7 +67,63 @@ pub struct Treasury {
 #[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct LzTokenTreasury {
     pub receiver: Pubkey,
-    pub fee: u64, // amount passed in is always 10^decimals
+    pub fee: u64, // amount passed in is always 10^decimals, used if the lz token fee bps is 0
+}
+
+/// The fee settings of the treasury added after the treasury. The lz token ones apply only if the
+/// treasury has the lz token treasury.
+#[derive(InitSpace, Clone, Default, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct TreasuryFees {
+    pub native_fee_bounds: TreasuryFeeBounds,
+    pub lz_token_fee_bps: u64, // bps of the worker fee, converted to lz token by the price ratio
+    pub lz_token_price_ratio: u128, // lz token per lamport scaled by LZ_TOKEN_PRICE_RATIO_DENOMINATOR
+    pub lz_token_fee_bounds: TreasuryFeeBounds,
+}
+
+/// The treasury program quoted for the fee of an oapp instead of the static fee settings. The fee
+/// bounds still apply to the quoted fee.
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct TreasuryProgram {
+    pub program: Pubkey,
+    pub config: Pubkey,
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, Default, PartialEq)]
+pub struct TreasuryFeeBounds {
+    pub min_fee: u64,
+    pub max_fee: Option<u64>,
+}
+
+impl Treasury {
+    pub fn fee_config(&self, fees: &TreasuryFees) -> TreasuryFeeConfig {
+        TreasuryFeeConfig {
+            native_fee_bps: self.native_fee_bps,
+            native_fee_bounds: fees.native_fee_bounds.clone(),
+            lz_token: self.lz_token.as_ref().map(|lz_token| LzTokenTreasuryFeeConfig {
+                fee: lz_token.fee,
+                fee_bps: fees.lz_token_fee_bps,
+                price_ratio: fees.lz_token_price_ratio,
+                fee_bounds: fees.lz_token_fee_bounds.clone(),
+            }),
+        }
+    }
+}
+
+impl TreasuryFeeBounds {
+    pub fn apply(&self, fee: u64) -> u64 {
+        let fee = fee.max(self.min_fee);
+        match self.max_fee {
+            Some(max_fee) => fee.min(max_fee),
+            None => fee,
+        }
+    }
+
+    pub fn sanity_check(&self) -> Result<()> {
+        if let Some(max_fee) = self.max_fee {
+            require!(self.min_fee <= max_fee, UlnError::InvalidTreasuryFeeCap);
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, InitSpace, AnchorSerialize, AnchorDeserialize, Default)]
@@ -75,6 +173,67 @@ pub struct SendConfig {
     pub bump: u8,
     pub uln: UlnConfig,
     pub executor: ExecutorConfig,
+    pub appended: Appended<SendConfigAppended>,
+}
+
+/// The fields appended to the send config after the accounts were created. They are read as the
+/// defaults until the account is migrated by extend_config().
+#[derive(InitSpace, Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct SendConfigAppended {
+    // set by the admin or the treasury admin, the treasury program accounts are passed on send
+    // only for the oapps with a treasury program
+    pub treasury_program: Option<TreasuryProgram>,
+    pub max_fees: WorkerMaxFees,
+    pub dvn_set: Option<Pubkey>, // replaces the optional dvns of the uln config if set
+}
+
+impl AppendedFields for SendConfigAppended {
+    const DISCRIMINATOR: [u8; 8] = *b"SndCfgEx";
+    const VERSION: u8 = 1;
+}
+
+impl SendConfig {
+    /// Sets the treasury program of the oapp, which requires the account to be migrated
+    pub fn set_treasury_program(&mut self, program: Option<TreasuryProgram>) -> Result<()> {
+        match self.appended.get_mut() {
+            Some(appended) => appended.treasury_program = program,
+            None => require!(program.is_none(), UlnError::NotMigrated),
+        }
+        Ok(())
+    }
+
+    pub fn treasury_program(&self) -> Option<&TreasuryProgram> {
+        self.appended.treasury_program.as_ref()
+    }
+
+    /// Sets the max fees of the workers, which requires the account to be migrated unless there
+    /// are none
+    pub fn set_max_fees(&mut self, fees: &[WorkerMaxFee]) -> Result<()> {
+        match self.appended.get_mut() {
+            Some(appended) => appended.max_fees.set(fees),
+            None => {
+                require!(fees.is_empty(), UlnError::NotMigrated);
+                Ok(())
+            },
+        }
+    }
+
+    pub fn max_fees(&self) -> &WorkerMaxFees {
+        &self.appended.max_fees
+    }
+
+    /// Sets the dvn set of the oapp, which requires the account to be migrated
+    pub fn set_dvn_set(&mut self, dvn_set: Option<Pubkey>) -> Result<()> {
+        match self.appended.get_mut() {
+            Some(appended) => appended.dvn_set = dvn_set,
+            None => require!(dvn_set.is_none(), UlnError::NotMigrated),
+        }
+        Ok(())
+    }
+
+    pub fn dvn_set(&self) -> Option<Pubkey> {
+        self.appended.dvn_set
+    }
 }
 
 #[account]
@@ -82,6 +241,74 @@ pub struct SendConfig {
 pub struct ReceiveConfig {
     pub bump: u8,
     pub uln: UlnConfig,
+    pub appended: Appended<ReceiveConfigAppended>,
+}
+
+/// The fields appended to the receive config after the accounts were created. They are read as
+/// the defaults until the account is migrated by extend_config().
+#[derive(InitSpace, Clone, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct ReceiveConfigAppended {
+    pub dvn_set: Option<Pubkey>, // replaces the optional dvns of the uln config if set
+    pub timeout: UlnConfigTimeout,
+}
+
+impl AppendedFields for ReceiveConfigAppended {
+    const DISCRIMINATOR: [u8; 8] = *b"RcvCfgEx";
+    const VERSION: u8 = 1;
+}
+
+impl ReceiveConfig {
+    /// Sets the dvn set of the oapp, which requires the account to be migrated
+    pub fn set_dvn_set(&mut self, dvn_set: Option<Pubkey>) -> Result<()> {
+        match self.appended.get_mut() {
+            Some(appended) => appended.dvn_set = dvn_set,
+            None => require!(dvn_set.is_none(), UlnError::NotMigrated),
+        }
+        Ok(())
+    }
+
+    pub fn dvn_set(&self) -> Option<Pubkey> {
+        self.appended.dvn_set
+    }
+
+    pub fn timeout(&self) -> &UlnConfigTimeout {
+        &self.appended.timeout
+    }
+
+    /// The timeout keeping the current config and dvn set verifying for the grace period from the
+    /// slot, or no timeout without a grace period
+    pub fn grace_period_timeout(&self, grace_period: u64, slot: u64) -> Result<UlnConfigTimeout> {
+        if grace_period == 0 {
+            return Ok(UlnConfigTimeout::default());
+        }
+        let expiry = slot.checked_add(grace_period).ok_or(UlnError::InvalidExpiry)?;
+        Ok(UlnConfigTimeout { uln: self.uln.clone(), dvn_set: self.dvn_set(), expiry })
+    }
+
+    /// Sets the previous config verifying until the timeout expires, which requires the account to
+    /// be migrated unless there is no timeout
+    pub fn set_timeout(&mut self, timeout: UlnConfigTimeout) -> Result<()> {
+        match self.appended.get_mut() {
+            Some(appended) => appended.timeout = timeout,
+            None => require!(timeout.expiry == 0, UlnError::NotMigrated),
+        }
+        Ok(())
+    }
+}
+
+/// The previous uln config and dvn set of a receive config, which still verify the packets in
+/// flight until the expiry slot
+#[derive(Clone, Default, InitSpace, AnchorSerialize, AnchorDeserialize)]
+pub struct UlnConfigTimeout {
+    pub uln: UlnConfig,
+    pub dvn_set: Option<Pubkey>,
+    pub expiry: u64, // slot number, 0 if there is no timeout
+}
+
+impl UlnConfigTimeout {
+    pub fn is_active(&self, slot: u64) -> bool {
+        self.expiry > slot
+    }
 }
 
 // the max data size that can be sent through a CPI is 1280 bytes
@@ -189,7 +416,7 @@ impl UlnConfig {
         Ok(())
     }
 
-    fn assert_no_duplicates(dvns: &Vec<Pubkey>) -> Result<()> {
+    pub(crate) fn assert_no_duplicates(dvns: &Vec<Pubkey>) -> Result<()> {
         let mut last_dvn = &Pubkey::default();
         for dvn in dvns {
             require!(dvn.cmp(&last_dvn) == Ordering::Greater, UlnError::Unsorted);
@@ -252,3 +479,178 @@ impl UlnConfig {
 utils::generate_account_size_test!(UlnSettings, uln_settings_test);
 utils::generate_account_size_test!(SendConfig, send_config_test);
 utils::generate_account_size_test!(ReceiveConfig, receive_config_test);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uln_settings(lz_token: Option<LzTokenTreasury>) -> UlnSettings {
+        UlnSettings {
+            eid: 1,
+            endpoint: Pubkey::new_unique(),
+            endpoint_program: Pubkey::new_unique(),
+            bump: 255,
+            admin: Pubkey::new_unique(),
+            treasury: Some(Treasury {
+                admin: None,
+                native_receiver: Pubkey::new_unique(),
+                native_fee_bps: 100,
+                lz_token,
+            }),
+            appended: Appended::default(),
+        }
+    }
+
+    #[test]
+    fn test_treasury_fees_not_migrated() {
+        // the account data left by the lz token treasury before it was removed
+        let lz_token = LzTokenTreasury { receiver: Pubkey::new_unique(), fee: u64::MAX };
+        let mut settings = uln_settings(Some(lz_token));
+        let mut data = vec![0; 8 + UlnSettings::INIT_SPACE];
+        settings.try_serialize(&mut &mut data[..]).unwrap();
+        settings.treasury.as_mut().unwrap().lz_token = None;
+        settings.try_serialize(&mut &mut data[..]).unwrap();
+
+        let mut settings = UlnSettings::try_deserialize(&mut &data[..]).unwrap();
+        assert!(settings.treasury.as_ref().unwrap().lz_token.is_none());
+        assert!(!settings.appended.is_migrated());
+        assert!(*settings.treasury_fees() == TreasuryFees::default());
+
+        // the treasury without the fees is set without the migration
+        let treasury = settings.treasury.clone();
+        settings.set_treasury(treasury.clone(), TreasuryFees::default()).unwrap();
+        let fees = TreasuryFees { lz_token_fee_bps: 100, ..Default::default() };
+        let result = settings.set_treasury(treasury, fees);
+        assert_eq!(result.unwrap_err(), UlnError::NotMigrated.into());
+        // and the fees are ignored without the treasury
+        settings
+            .set_treasury(None, TreasuryFees { lz_token_fee_bps: 100, ..Default::default() })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_treasury_fees_migrated() {
+        let lz_token = LzTokenTreasury { receiver: Pubkey::new_unique(), fee: 1000 };
+        let mut settings = uln_settings(Some(lz_token));
+        settings.appended.migrate();
+        let fees = TreasuryFees {
+            native_fee_bounds: TreasuryFeeBounds { min_fee: 1, max_fee: Some(2) },
+            lz_token_fee_bps: 100,
+            lz_token_price_ratio: 3,
+            lz_token_fee_bounds: TreasuryFeeBounds { min_fee: 4, max_fee: None },
+        };
+        let treasury = settings.treasury.clone();
+        settings.set_treasury(treasury, fees.clone()).unwrap();
+
+        let mut data = vec![0; 8 + UlnSettings::INIT_SPACE];
+        settings.try_serialize(&mut &mut data[..]).unwrap();
+        let mut settings = UlnSettings::try_deserialize(&mut &data[..]).unwrap();
+        assert!(*settings.treasury_fees() == fees);
+
+        // the fees are read after the shorter treasury
+        let mut treasury = settings.treasury.clone().unwrap();
+        treasury.lz_token = None;
+        settings.set_treasury(Some(treasury), fees.clone()).unwrap();
+        settings.try_serialize(&mut &mut data[..]).unwrap();
+        let mut settings = UlnSettings::try_deserialize(&mut &data[..]).unwrap();
+        assert!(settings.treasury.as_ref().unwrap().lz_token.is_none());
+        assert!(*settings.treasury_fees() == fees);
+
+        // and reset without the treasury
+        settings.set_treasury(None, fees).unwrap();
+        settings.try_serialize(&mut &mut data[..]).unwrap();
+        let settings = UlnSettings::try_deserialize(&mut &data[..]).unwrap();
+        assert!(settings.appended.is_migrated());
+        assert!(*settings.treasury_fees() == TreasuryFees::default());
+    }
+
+    #[test]
+    fn test_set_send_config_treasury_program() {
+        let program =
+            TreasuryProgram { program: Pubkey::new_unique(), config: Pubkey::new_unique() };
+        let mut config = SendConfig::default();
+        assert_eq!(
+            config.set_treasury_program(Some(program.clone())).unwrap_err(),
+            UlnError::NotMigrated.into()
+        );
+        config.set_treasury_program(None).unwrap();
+        assert!(config.treasury_program().is_none());
+
+        config.appended.migrate();
+        config.set_treasury_program(Some(program.clone())).unwrap();
+        let mut data = vec![0; 8 + SendConfig::INIT_SPACE];
+        config.try_serialize(&mut &mut data[..]).unwrap();
+        let config = SendConfig::try_deserialize(&mut &data[..]).unwrap();
+        assert!(config.treasury_program() == Some(&program));
+    }
+
+    #[test]
+    fn test_set_send_config_max_fees() {
+        let max_fee = WorkerMaxFee { worker: Pubkey::new_unique(), fee_token: None, max_fee: 1 };
+        let mut config = SendConfig::default();
+        assert_eq!(
+            config.set_max_fees(&[max_fee.clone()]).unwrap_err(),
+            UlnError::NotMigrated.into()
+        );
+        config.set_max_fees(&[]).unwrap();
+
+        config.appended.migrate();
+        config.set_max_fees(&[max_fee.clone()]).unwrap();
+        let mut data = vec![0; 8 + SendConfig::INIT_SPACE];
+        config.try_serialize(&mut &mut data[..]).unwrap();
+        let config = SendConfig::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(config.max_fees().fees, vec![max_fee]);
+    }
+
+    #[test]
+    fn test_receive_config_timeout_not_migrated() {
+        // the receive config with the stale bytes of a longer uln config left after its data
+        let dvns = (0..DVN_MAX_LEN).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let mut config = ReceiveConfig::default();
+        config.uln.optional_dvns = dvns;
+        let mut data = vec![0; 8 + ReceiveConfig::INIT_SPACE];
+        config.try_serialize(&mut &mut data[..]).unwrap();
+        config.uln.optional_dvns.clear();
+        config.try_serialize(&mut &mut data[..]).unwrap();
+
+        let mut config = ReceiveConfig::try_deserialize(&mut &data[..]).unwrap();
+        assert!(!config.appended.is_migrated());
+        assert_eq!(config.timeout().expiry, 0);
+
+        let timeout = UlnConfigTimeout { expiry: 100, ..Default::default() };
+        assert_eq!(config.set_timeout(timeout.clone()).unwrap_err(), UlnError::NotMigrated.into());
+        config.set_timeout(UlnConfigTimeout::default()).unwrap();
+
+        config.appended.migrate();
+        config.set_timeout(timeout).unwrap();
+        config.try_serialize(&mut &mut data[..]).unwrap();
+        let config = ReceiveConfig::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(config.timeout().expiry, 100);
+    }
+
+    #[test]
+    fn test_config_dvn_set_not_migrated() {
+        // the send config with the stale bytes of a longer uln config left after its data
+        let mut config = SendConfig::default();
+        config.uln.optional_dvns = vec![Pubkey::new_unique(); DVN_MAX_LEN as usize];
+        let mut data = vec![0; 8 + SendConfig::INIT_SPACE];
+        config.try_serialize(&mut &mut data[..]).unwrap();
+        config.uln.optional_dvns.clear();
+        config.try_serialize(&mut &mut data[..]).unwrap();
+
+        let mut config = SendConfig::try_deserialize(&mut &data[..]).unwrap();
+        assert!(config.dvn_set().is_none());
+        let dvn_set = Some(Pubkey::new_unique());
+        assert_eq!(config.set_dvn_set(dvn_set).unwrap_err(), UlnError::NotMigrated.into());
+        config.appended.migrate();
+        config.set_dvn_set(dvn_set).unwrap();
+        assert_eq!(config.dvn_set(), dvn_set);
+
+        let mut config = ReceiveConfig::default();
+        assert_eq!(config.set_dvn_set(dvn_set).unwrap_err(), UlnError::NotMigrated.into());
+        config.set_dvn_set(None).unwrap();
+        config.appended.migrate();
+        config.set_dvn_set(dvn_set).unwrap();
+        assert_eq!(config.dvn_set(), dvn_set);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::*;
+
+pub const WORKER_MAX_FEES_MAX_LEN: usize = 8;
+
+/// The max fees of the workers of a send config, checked on send to protect the oapp from the
+/// fees quoted by a misconfigured worker or price feed
+#[derive(Clone, Default, InitSpace, AnchorSerialize, AnchorDeserialize)]
+pub struct WorkerMaxFees {
+    #[max_len(WORKER_MAX_FEES_MAX_LEN)]
+    pub fees: Vec<WorkerMaxFee>,
+}
+
+#[derive(Clone, InitSpace, AnchorSerialize, AnchorDeserialize, PartialEq, Debug)]
+pub struct WorkerMaxFee {
+    pub worker: Pubkey,
+    pub fee_token: Option<Pubkey>, // the mint of the fee token, None for lamports
+    pub max_fee: u64,              // in the unit of the fee token
+}
+
+impl WorkerMaxFees {
+    pub fn set(&mut self, fees: &[WorkerMaxFee]) -> Result<()> {
+        require!(fees.len() <= WORKER_MAX_FEES_MAX_LEN, UlnError::InvalidWorkerMaxFees);
+        for (i, fee) in fees.iter().enumerate() {
+            require!(
+                !fees[..i]
+                    .iter()
+                    .any(|other| other.worker == fee.worker && other.fee_token == fee.fee_token),
+                UlnError::InvalidWorkerMaxFees
+            );
+        }
+        self.fees = fees.to_vec();
+        Ok(())
+    }
+
+    /// Asserts the fee of the worker does not exceed its max fee in the unit it is paid in, if the
+    /// worker has max fees. A worker paid in a unit without a max fee is rejected, so that the max
+    /// fee in one unit is never compared with the fee in another.
+    pub fn check(&self, worker_fee: &WorkerFee) -> Result<()> {
+        let mut max_fees = self
+            .fees
+            .iter()
+            .filter(|max_fee| max_fee.worker == worker_fee.worker)
+            .peekable();
+        if max_fees.peek().is_none() {
+            return Ok(());
+        }
+        match max_fees.find(|max_fee| max_fee.fee_token == worker_fee.fee_token) {
+            Some(max_fee) if worker_fee.fee > max_fee.max_fee => {
+                msg!(
+                    "worker {} fee {} > max fee {}",
+                    worker_fee.worker,
+                    worker_fee.fee,
+                    max_fee.max_fee
+                );
+                err!(UlnError::ExceededWorkerMaxFee)
+            },
+            Some(_) => Ok(()),
+            None => {
+                msg!("worker {} has no max fee in {:?}", worker_fee.worker, worker_fee.fee_token);
+                err!(UlnError::WorkerMaxFeeUnavailable)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_worker_max_fees() {
+        let mut max_fees = WorkerMaxFees::default();
+        let fee = |worker: Pubkey| WorkerMaxFee { worker, fee_token: None, max_fee: 100 };
+        let worker = Pubkey::new_unique();
+        assert!(max_fees.set(&[fee(worker), fee(Pubkey::new_unique())]).is_ok());
+        assert_eq!(max_fees.fees.len(), 2);
+
+        // the max fees of a worker in different units
+        let token_fee = WorkerMaxFee { fee_token: Some(Pubkey::new_unique()), ..fee(worker) };
+        assert!(max_fees.set(&[fee(worker), token_fee.clone()]).is_ok());
+        assert_eq!(max_fees.fees.len(), 2);
+
+        // duplicate workers
+        assert!(max_fees.set(&[fee(worker), fee(worker)]).is_err());
+        assert!(max_fees.set(&[token_fee.clone(), token_fee]).is_err());
+        // too many workers
+        let fees = (0..=WORKER_MAX_FEES_MAX_LEN)
+            .map(|_| fee(Pubkey::new_unique()))
+            .collect::<Vec<_>>();
+        assert!(max_fees.set(&fees).is_err());
+        assert_eq!(max_fees.fees.len(), 2);
+    }
+
+    #[test]
+    fn test_check_worker_max_fees() {
+        let worker = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let max_fees = WorkerMaxFees {
+            fees: vec![
+                WorkerMaxFee { worker, fee_token: None, max_fee: 100 },
+                WorkerMaxFee { worker, fee_token: Some(mint), max_fee: 10 },
+            ],
+        };
+        let worker_fee = |worker: Pubkey, fee: u64, fee_token: Option<Pubkey>| WorkerFee {
+            worker,
+            fee,
+            fee_token,
+        };
+
+        assert!(max_fees.check(&worker_fee(worker, 100, None)).is_ok());
+        assert_eq!(
+            max_fees.check(&worker_fee(worker, 101, None)).unwrap_err(),
+            UlnError::ExceededWorkerMaxFee.into()
+        );
+        // the fee in the fee token is checked against the max fee in the fee token
+        assert!(max_fees.check(&worker_fee(worker, 10, Some(mint))).is_ok());
+        assert_eq!(
+            max_fees.check(&worker_fee(worker, 11, Some(mint))).unwrap_err(),
+            UlnError::ExceededWorkerMaxFee.into()
+        );
+        // and a fee in another unit is rejected
+        assert_eq!(
+            max_fees.check(&worker_fee(worker, 1, Some(Pubkey::new_unique()))).unwrap_err(),
+            UlnError::WorkerMaxFeeUnavailable.into()
+        );
+        // the workers without a max fee
+        assert!(max_fees.check(&worker_fee(Pubkey::new_unique(), u64::MAX, None)).is_ok());
+    }
+}
@@ -159,24 +159,62 @@ mod test_options_codec {
     }
 
     #[test]
-    #[should_panic]
     fn test_decode_type3_options_invalid_size_longer() {
         // case 1: add one more byte to make it invalid
         let mut t3_option = OptionsUtil::new_options();
         t3_option.add_executor_lz_receive_option(20000, 0);
         t3_option.value.push(1);
         t3_option.value.push(2);
-        assert!(options_codec::decode_options(&t3_option.value).is_err());
+        let result = options_codec::decode_options(&t3_option.value);
+        assert!(result.unwrap_err().to_string().contains("TruncatedOptionHeader"));
     }
 
     #[test]
-    #[should_panic]
     fn test_decode_type3_options_invalid_size_shorter() {
         // case 2: remove the last byte to make it invalid
         let mut t3_option = OptionsUtil::new_options();
         t3_option.add_executor_lz_receive_option(20000, 0);
         t3_option.value.pop();
-        assert!(options_codec::decode_options(&t3_option.value).is_err());
+        let result = options_codec::decode_options(&t3_option.value);
+        assert!(result.unwrap_err().to_string().contains("OptionOverrun"));
+    }
+
+    #[test]
+    fn test_decode_type3_options_zero_size() {
+        let mut options = OptionsUtil::new_options().value;
+        options.extend_from_slice(&[options_codec::EXECUTOR_WORKER_ID, 0, 0]);
+        let result = options_codec::decode_options(&options);
+        assert!(result.unwrap_err().to_string().contains("ZeroSizeOption"));
+
+        // a dvn option needs at least the dvn index and the option type
+        let mut options = OptionsUtil::new_options().value;
+        options.extend_from_slice(&[options_codec::DVN_WORKER_ID, 0, 1, 1]);
+        let result = options_codec::decode_options(&options);
+        assert!(result.unwrap_err().to_string().contains("InvalidDvnOptionSize"));
+    }
+
+    #[test]
+    fn test_decode_options_too_short() {
+        for options in [vec![], vec![0]] {
+            let result = options_codec::decode_options(&options);
+            assert!(result.unwrap_err().to_string().contains("TruncatedOptionHeader"));
+        }
+    }
+
+    #[test]
+    fn test_decode_type2_invalid_receiver_size() {
+        let legacy_options = OptionsUtil::encode_legacy_options_type2(
+            U256::from(20000),
+            U256::from(10000),
+            [1u8; 32],
+        );
+        let result = options_codec::decode_options(&legacy_options[..legacy_options.len() - 1]);
+        assert!(result.unwrap_err().to_string().contains("InvalidType2Size"));
+
+        let mut longer = legacy_options.clone();
+        longer.push(1);
+        let result = options_codec::decode_options(&longer);
+        assert!(result.unwrap_err().to_string().contains("InvalidType2Size"));
     }
 
     #[test]
@@ -199,3 +237,77 @@ mod test_options_codec {
         assert!(result.unwrap_err().to_string().contains("InvalidOptionType"));
     }
 }
+
+#[cfg(test)]
+mod test_options_codec_fuzz {
+    use crate::utils::options_util::OptionsUtil;
+    use proptest::prelude::*;
+    use uln::options_codec;
+
+    // (is_executor, dvn_idx, option_type, params)
+    fn worker_option() -> impl Strategy<Value = (bool, u8, u8, Vec<u8>)> {
+        (any::<bool>(), any::<u8>(), any::<u8>(), proptest::collection::vec(any::<u8>(), 0..64))
+    }
+
+    proptest! {
+        #[test]
+        fn decode_arbitrary_bytes_never_panics(options in proptest::collection::vec(any::<u8>(), 0..256)) {
+            let _ = options_codec::decode_options(&options);
+        }
+
+        #[test]
+        fn decode_arbitrary_type3_never_panics(body in proptest::collection::vec(any::<u8>(), 0..256)) {
+            let options = [options_codec::TYPE_3.to_be_bytes().to_vec(), body].concat();
+            let _ = options_codec::decode_options(&options);
+        }
+
+        #[test]
+        fn decode_type3_roundtrip(worker_options in proptest::collection::vec(worker_option(), 0..16)) {
+            let mut t3_option = OptionsUtil::new_options();
+            for (is_executor, dvn_idx, option_type, params) in worker_options.iter().cloned() {
+                if is_executor {
+                    t3_option.add_option(options_codec::EXECUTOR_WORKER_ID, option_type, params);
+                } else {
+                    t3_option.add_dvn_option(dvn_idx, option_type, params);
+                }
+            }
+
+            let (executor_options, dvn_options) =
+                options_codec::decode_options(&t3_option.value).unwrap();
+            let expected_executor_options: Vec<_> =
+                worker_options.iter().filter(|(is_executor, ..)| *is_executor).collect();
+            prop_assert_eq!(executor_options.len(), expected_executor_options.len());
+            for (option, (_, _, option_type, params)) in
+                executor_options.iter().zip(expected_executor_options)
+            {
+                prop_assert_eq!(option.option_type, *option_type);
+                prop_assert_eq!(&option.params, params);
+            }
+
+            let dvn_option_count: usize = dvn_options.values().map(|options| options.len()).sum();
+            prop_assert_eq!(dvn_option_count, worker_options.len() - executor_options.len());
+        }
+
+        #[test]
+        fn decode_truncated_type3_returns_error(
+            worker_options in proptest::collection::vec(worker_option(), 1..8),
+            cut in any::<usize>(),
+        ) {
+            let mut t3_option = OptionsUtil::new_options();
+            let mut last_option_start = 0;
+            for (is_executor, dvn_idx, option_type, params) in worker_options {
+                last_option_start = t3_option.value.len();
+                if is_executor {
+                    t3_option.add_option(options_codec::EXECUTOR_WORKER_ID, option_type, params);
+                } else {
+                    t3_option.add_dvn_option(dvn_idx, option_type, params);
+                }
+            }
+
+            // cutting into the last worker option always leaves a truncated header or option
+            let last_option_len = t3_option.value.len() - last_option_start;
+            let len = t3_option.value.len() - (cut % (last_option_len - 1) + 1);
+            prop_assert!(options_codec::decode_options(&t3_option.value[..len]).is_err());
+        }
+    }
+}
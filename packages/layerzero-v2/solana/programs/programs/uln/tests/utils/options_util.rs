@@ -59,7 +59,7 @@ impl OptionsUtil {
         self.add_option(EXECUTOR_WORKER_ID, option_type, option);
     }
 
-    fn add_dvn_option(&mut self, dvn_idx: u8, option_type: u8, option: Vec<u8>) {
+    pub fn add_dvn_option(&mut self, dvn_idx: u8, option_type: u8, option: Vec<u8>) {
         let bytes = [
             self.value.clone(),
             DVN_WORKER_ID.to_be_bytes().to_vec(),
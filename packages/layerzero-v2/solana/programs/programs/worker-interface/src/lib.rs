@@ -15,6 +15,22 @@ pub mod worker_interface {
     pub fn quote_dvn(_ctx: Context<Quote>, _params: QuoteDvnParams) -> Result<u64> {
         Ok(0)
     }
+
+    /// Returns None if the executor does not accept fees in an spl token
+    pub fn quote_executor_in_token(
+        _ctx: Context<Quote>,
+        _params: QuoteExecutorParams,
+    ) -> Result<Option<TokenFee>> {
+        Ok(None)
+    }
+
+    /// Returns None if the dvn does not accept fees in an spl token
+    pub fn quote_dvn_in_token(
+        _ctx: Context<Quote>,
+        _params: QuoteDvnParams,
+    ) -> Result<Option<TokenFee>> {
+        Ok(None)
+    }
 }
 
 #[derive(Accounts)]
@@ -50,6 +66,13 @@ pub struct LzOption {
     pub params: Vec<u8>,
 }
 
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct TokenFee {
+    pub mint: Pubkey,
+    pub fee: u64,
+    pub native_fee: u64, // the fee in lamports that is converted to the token
+}
+
 #[error_code]
 pub enum WorkerError {
     PermissionDenied,
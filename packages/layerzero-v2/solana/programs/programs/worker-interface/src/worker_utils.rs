@@ -2,6 +2,7 @@ use crate::*;
 
 pub const SOL_DECIMALS_RATE: u128 = u128::pow(10, 9);
 pub const ACL_MAX_LEN: usize = 8;
+pub const FEE_TOKEN_PRICE_RATIO_DENOMINATOR: u128 = u128::pow(10, 20);
 
 #[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct Acl {
@@ -36,6 +37,23 @@ impl Acl {
     }
 }
 
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct FeeToken {
+    pub mint: Pubkey,
+    // the amount of the token in its smallest unit per lamport, scaled by FEE_TOKEN_PRICE_RATIO_DENOMINATOR
+    pub price_ratio: u128,
+}
+
+impl FeeToken {
+    pub fn convert(&self, native_fee: u64) -> Result<TokenFee> {
+        let fee = (native_fee as u128)
+            .checked_mul(self.price_ratio)
+            .ok_or(WorkerError::InvalidSize)?
+            / FEE_TOKEN_PRICE_RATIO_DENOMINATOR;
+        Ok(TokenFee { mint: self.mint, fee: safe_convert_u128_to_u64(fee)?, native_fee })
+    }
+}
+
 pub fn safe_convert_u128_to_u64(value: u128) -> Result<u64> {
     require!(value <= u64::MAX as u128, WorkerError::InvalidSize);
     Ok(value as u64)